@@ -0,0 +1,77 @@
+// Spawns the workers of the thread pool that is enabled by the `threads`
+// feature. Workers are `node:worker_threads` in Node.js and Web Workers
+// otherwise. Either way they load this very file, instantiate the shared wasm
+// module, and hand their thread over to rayon.
+
+const INIT = 'rosu_pp_js_worker_init';
+const READY = 'rosu_pp_js_worker_ready';
+
+const isNode = typeof process === 'object' && typeof process.versions?.node === 'string';
+
+// Keeps the workers referenced so they are not garbage collected
+let workers;
+
+function waitForMsgType(target, type) {
+  return new Promise((resolve) => {
+    const onMsg = (msg) => {
+      const data = isNode ? msg : msg.data;
+
+      if (data?.type !== type) return;
+
+      if (isNode) {
+        target.off('message', onMsg);
+      } else {
+        target.removeEventListener('message', onMsg);
+      }
+
+      resolve(data);
+    };
+
+    if (isNode) {
+      target.on('message', onMsg);
+    } else {
+      target.addEventListener('message', onMsg);
+    }
+  });
+}
+
+async function runWorker(port, { mainJS, init }) {
+  // Imported dynamically because the main module imports this file
+  const pkg = await import(mainJS);
+  await pkg.default(init);
+  port.postMessage({ type: READY });
+  pkg.startPoolWorker();
+}
+
+if (isNode) {
+  import('node:worker_threads').then(({ parentPort, workerData }) => {
+    if (workerData?.type === INIT) runWorker(parentPort, workerData);
+  });
+} else if (typeof self === 'object') {
+  waitForMsgType(self, INIT).then((data) => runWorker(self, data));
+}
+
+export async function startWorkers(module, memory, mainJS, numThreads) {
+  const data = { type: INIT, mainJS, init: { module_or_path: module, memory } };
+  const { Worker } = isNode ? await import('node:worker_threads') : globalThis;
+
+  workers = await Promise.all(
+    Array.from({ length: numThreads }, async () => {
+      let worker;
+
+      if (isNode) {
+        worker = new Worker(new URL(import.meta.url), { workerData: data });
+      } else {
+        worker = new Worker(new URL(import.meta.url), { type: 'module' });
+        worker.postMessage(data);
+      }
+
+      await waitForMsgType(worker, READY);
+
+      // Workers never finish so they must not keep the process alive
+      if (isNode) worker.unref();
+
+      return worker;
+    })
+  );
+}
@@ -6,6 +6,63 @@ export enum GameMode {
   Catch = 2,
   Mania = 3,
 }
+/**
+ * The grade of a score.
+ */
+export enum Grade {
+  /**
+   * Silver SS, i.e. SS with `HD` or `FL`
+   */
+  SSH = 0,
+  /**
+   * SS, i.e. 100% accuracy
+   */
+  SS = 1,
+  /**
+   * Silver S, i.e. S with `HD` or `FL`
+   */
+  SH = 2,
+  S = 3,
+  A = 4,
+  B = 5,
+  C = 6,
+  D = 7,
+}
+/**
+ * The kind of a hitobject.
+ */
+export enum HitObjectKind {
+  Circle = 0,
+  Slider = 1,
+  Spinner = 2,
+  Hold = 3,
+}
+/**
+ * The judgement of a hit object.
+ */
+export enum HitResult {
+  Miss = 0,
+  /**
+   * 50
+   */
+  Meh = 1,
+  /**
+   * 100
+   */
+  Ok = 2,
+  /**
+   * 200 in osu!mania, otherwise treated as `Ok`
+   */
+  Good = 3,
+  /**
+   * 300
+   */
+  Great = 4,
+  /**
+   * 320 in osu!mania, otherwise treated as `Great`
+   */
+  Perfect = 5,
+}
 /**
  * While generating remaining hitresults, decide how they should be distributed.
  */
@@ -23,6 +80,81 @@ export enum HitResultPriority {
    */
   Fastest = 2,
 }
+/**
+* The result of `Difficulty.analyzeStrains`.
+*
+* Skill analyses are only available for the mode they belong to.
+*/
+export interface StrainAnalysis {
+    /**
+    * The strains' gamemode.
+    */
+    mode: GameMode;
+    aim?: SkillAnalysis;
+    aimNoSliders?: SkillAnalysis;
+    speed?: SkillAnalysis;
+    flashlight?: SkillAnalysis;
+    color?: SkillAnalysis;
+    reading?: SkillAnalysis;
+    rhythm?: SkillAnalysis;
+    stamina?: SkillAnalysis;
+    singleColorStamina?: SkillAnalysis;
+    movement?: SkillAnalysis;
+    strains?: SkillAnalysis;
+}
+
+/**
+* Where the difficulty of a skill lies within a beatmap.
+*/
+export interface SkillAnalysis {
+    /**
+    * How concentrated the difficulty is, i.e. the highest strain peak
+    * divided by the average strain peak.
+    *
+    * `1` means that the difficulty is spread evenly across the beatmap,
+    * higher values indicate spikes. `0` if there are no strains.
+    */
+    spikiness: number;
+    /**
+    * The hardest sections sorted by their average strain, hardest first.
+    *
+    * Sections do not overlap.
+    */
+    sections: HardestSection[];
+}
+
+/**
+* A section with particularly high strain.
+*/
+export interface HardestSection {
+    /**
+    * Start time of the section in ms.
+    *
+    * The time is the beatmap's time, i.e. it is not adjusted by the clock
+    * rate.
+    */
+    startTime: number;
+    /**
+    * End time of the section in ms.
+    */
+    endTime: number;
+    /**
+    * The highest strain peak within the section.
+    */
+    peak: number;
+    /**
+    * The average strain peak within the section.
+    */
+    average: number;
+    /**
+    * Indices of the hitobjects within the section.
+    *
+    * For osu!taiko only circles and for osu!catch only fruits and droplets
+    * are considered hitobjects.
+    */
+    objects: Uint32Array;
+}
+
 /**
 * The content of a `.osu` file either as bytes or string.
 */
@@ -168,6 +300,120 @@ export interface DifficultyArgs extends CommonArgs {
     * Defaults to `true`.
     */
     lazer?: boolean;
+    /**
+    * Store calculated difficulty attributes in the given cache and reuse
+    * them when calculating on the same beatmap with the same settings.
+    */
+    cache?: DifficultyCache;
+}
+
+/**
+* Options for `Difficulty.strains`.
+*/
+export interface StrainsArgs {
+    /**
+    * Whether to include a strain value and start time for each hitobject,
+    * available through `Strains.objects`.
+    *
+    * Defaults to `false`.
+    */
+    perObject?: boolean;
+    /**
+    * Whether to include the start time of each strain section, available
+    * through `Strains.sectionTimes`.
+    *
+    * Defaults to `false`.
+    */
+    sectionTimes?: boolean;
+}
+
+/**
+* Options for `Strains.resample`.
+*/
+export interface StrainsResampleArgs {
+    /**
+    * The amount of neighboring buckets on each side to average over.
+    *
+    * Defaults to `0`, i.e. no smoothing.
+    */
+    smoothing?: number;
+    /**
+    * Whether to scale each series so that its maximum is `1`.
+    *
+    * Defaults to `false`.
+    */
+    normalize?: boolean;
+}
+
+/**
+* Options for `Difficulty.analyzeStrains`.
+*/
+export interface StrainAnalysisArgs {
+    /**
+    * The maximum amount of hardest sections per skill.
+    *
+    * Defaults to `3`.
+    */
+    count?: number;
+    /**
+    * The length of a section in ms.
+    *
+    * Same as the returned times, the length is not adjusted by the clock
+    * rate. It is rounded to a multiple of the strain section length.
+    *
+    * Defaults to `4000`.
+    */
+    windowLength?: number;
+}
+
+/**
+* The judgement of a single hit object for `GradualPerformance.nextHit`.
+*/
+export interface HitEvent {
+    /**
+    * The hitresult of the hit object itself.
+    *
+    * For osu!catch, fruits are either `Great` or `Miss` and droplets are
+    * either `Ok` or `Miss`. Tiny droplets are not considered hit objects;
+    * specify them through `smallTickHits` and `smallTickMisses` of the
+    * following fruit or droplet instead.
+    */
+    result: HitResult;
+    /**
+    * The amount of hit "large ticks" that belong to this hit object.
+    *
+    * Only relevant for osu!standard sliders, see
+    * `PerformanceArgs.largeTickHits`.
+    */
+    largeTickHits?: number;
+    /**
+    * The amount of hit "small ticks" that belong to this hit object.
+    *
+    * Relevant for osu!standard sliders, see
+    * `PerformanceArgs.smallTickHits`, and osu!catch for which it represents
+    * the amount of hit tiny droplets.
+    */
+    smallTickHits?: number;
+    /**
+    * The amount of missed tiny droplets.
+    *
+    * Only relevant for osu!catch.
+    */
+    smallTickMisses?: number;
+    /**
+    * Whether the slider end was hit.
+    *
+    * Only relevant for osu!standard sliders in lazer.
+    */
+    sliderEndHit?: boolean;
+    /**
+    * The current combo after this hit object.
+    *
+    * If not specified, the combo is tracked by incrementing it for each
+    * hit object as well as hit large ticks and slider ends, and resetting it
+    * on misses.
+    */
+    combo?: number;
 }
 
 /**
@@ -251,118 +497,901 @@ export interface PerformanceArgs extends DifficultyArgs {
     hitresultPriority?: HitResultPriority;
 }
 
+/**
+* Arguments to provide `Performance.calculateScenarios`.
+*/
+export interface PerformanceScenariosArgs {
+    /**
+    * Accuracies between `0.0` and `100.0` for which the performance of a
+    * full combo should be calculated.
+    */
+    accuracies?: number[];
+}
+
+/**
+* The argument that `Performance.solveForPp` varies.
+*/
+export type SolveForPpVariable = "accuracy" | "misses" | "combo";
+
+/**
+* Arguments to provide `Performance.solveForPp`.
+*/
+export interface SolveForPpArgs {
+    /**
+    * The argument to search over.
+    *
+    * Defaults to `"accuracy"`.
+    */
+    vary?: SolveForPpVariable;
+}
+
 /**
 * Either previously calculated attributes or a beatmap.
 */
 export type MapOrAttributes = DifficultyAttributes | PerformanceAttributes | Beatmap;
 
 /**
-* Arguments to provide the `Difficulty` constructor.
+* Arguments to provide `ScoreStateUtils.fromAccuracy`.
 */
-export interface ScoreState {
+export interface StateFromAccuracyArgs {
     /**
-    * Maximum combo that the score has had so far. **Not** the maximum
-    * possible combo of the map so far.
+    * The accuracy between `0.0` and `100.0`.
     *
-    * Note that for osu!catch only fruits and droplets are considered for
-    * combo.
+    * Defaults to `100.0`.
+    */
+    accuracy?: number;
+    /**
+    * The amount of misses.
+    */
+    misses?: number;
+    /**
+    * The max combo of the play.
     *
-    * Irrelevant for osu!mania.
+    * Defaults to the maximum possible combo considering misses.
     */
-    maxCombo?: number;
+    combo?: number;
+    /**
+    * Whether the score was set on osu!lazer or osu!stable.
+    *
+    * Defaults to `true`.
+    */
+    lazer?: boolean;
+    /**
+    * Specify mods.
+    *
+    * Only relevant for the `CL` mod which affects how slider ticks and
+    * osu!mania's hitresults are weighted in osu!lazer.
+    *
+    * See `CommonArgs.mods` for the accepted types.
+    */
+    mods?: Object;
+    /**
+    * Specify how hitresults should be generated.
+    *
+    * Defaults to `HitResultPriority.BestCase`.
+    */
+    priority?: HitResultPriority;
+}
 
+/**
+* Arguments to provide `ScoreStateUtils.accuracy` and `ScoreStateUtils.grade`.
+*/
+export interface StateAccuracyArgs {
     /**
-    * "Large tick" hits for osu!standard.
+    * Whether the score was set on osu!lazer or osu!stable.
     *
-    * The meaning depends on the kind of score:
-    * - if set on osu!stable, this field is irrelevant and can be `0`
-    * - if set on osu!lazer *without* `CL`, this field is the amount of hit
-    *   slider ticks and repeats
-    * - if set on osu!lazer *with* `CL`, this field is the amount of hit
-    *   slider heads, ticks, and repeats
+    * Defaults to `true`.
     */
-    osuLargeTickHits?: number;
+    lazer?: boolean;
+    /**
+    * Specify mods.
+    *
+    * Relevant for the `CL` mod which affects how slider ticks and osu!mania's
+    * hitresults are weighted in osu!lazer, as well as for silver grades.
+    *
+    * See `CommonArgs.mods` for the accepted types.
+    */
+    mods?: Object;
+    /**
+    * Difficulty attributes of the beatmap.
+    *
+    * Only relevant for osu!lazer scores in osu! to determine the maximum
+    * amount of slider-related hitresults.
+    */
+    attributes?: DifficultyAttributes;
+}
 
+/**
+* Arguments to provide `ScoreStateUtils.validate`.
+*/
+export interface StateValidationArgs {
     /**
-    * "Small tick" hits for osu!standard.
+    * Whether the score was set on osu!lazer or osu!stable.
     *
-    * These are essentially the slider end hits for lazer scores without
-    * slider accuracy.
+    * Defaults to `true`.
+    */
+    lazer?: boolean;
+    /**
+    * Specify mods.
     *
-    * Only relevant for osu!lazer.
-    */ 
-    osuSmallTickHits?: number;
+    * Relevant for the `CL` mod which affects the amount of osu!standard
+    * slider-related hitresults and osu!mania's hitresults in osu!lazer.
+    *
+    * See `CommonArgs.mods` for the accepted types.
+    */
+    mods?: Object;
+}
 
+/**
+* Why a beatmap appears too suspicious for further calculation.
+*/
+export interface SuspicionReport {
     /**
-    * Amount of successfully hit slider ends.
+    * The kind of suspicion.
     *
-    * Only relevant for osu!standard in lazer.
+    * - `objectCount`: The beatmap has too many hitobjects.
+    * - `length`: The time between the first and last hitobject is too long.
+    * - `density`: Too many hitobjects appear within a short time.
+    * - `redFlag`: A slider has both an absurd amount of repeats and an
+    *   absurd position.
+    * - `sliderPositions`: Too many sliders have absurd positions.
+    * - `sliderRepeats`: Too many sliders have an absurd amount of repeats.
+    * - `unknown`: A kind that is not covered yet.
     */
-    sliderEndHits?: number;
-    
+    reason: "objectCount" | "length" | "density" | "redFlag" | "sliderPositions" | "sliderRepeats" | "unknown";
     /**
-    * Amount of current gekis (n320 for osu!mania).
+    * The offending value, i.e. the amount of hitobjects for `objectCount`
+    * and `density`, the time in ms for `length`, and the amount of
+    * offending sliders otherwise.
     */
-    nGeki?: number;
+    value: number;
     /**
-    * Amount of current katus (tiny droplet misses for osu!catch / n200 for
-    * osu!mania).
+    * The threshold that `value` exceeds.
     */
-    nKatu?: number;
+    threshold: number;
     /**
-    * Amount of current 300s (fruits for osu!catch).
+    * Start time in ms of the first offending hitobject.
     */
-    n300?: number;
+    startTime?: number;
     /**
-    * Amount of current 100s (droplets for osu!catch).
+    * Start time in ms of the last offending hitobject.
     */
-    n100?: number;
+    endTime?: number;
     /**
-    * Amount of current 50s (tiny droplets for osu!catch).
+    * Indices of the offending hitobjects.
+    *
+    * Empty for `objectCount` and `unknown`.
     */
-    n50?: number;
+    objects: Uint32Array;
     /**
-    * Amount of current misses (fruits + droplets for osu!catch).
+    * Human-readable description of the suspicion.
     */
-    misses?: number;
+    message: string;
 }
 
 /**
- * All beatmap data that is relevant for difficulty and performance
- * calculation.
- *
- * It is recommended to call the method `Beatmap.free` on instances that are
- * no longer in use to avoid the risk of leaking memory.
- */
-export class Beatmap {
-  free(): void;
-  /**
-   * Create a new beatmap instance by parsing an `.osu` file's content.
-   * @throws Throws an error if decoding the map failed
-   */
-  constructor(args: BeatmapContent);
-  /**
-   * Convert a beatmap to a specific mode.
-   * @throws Throws an error if conversion fails or mods are invalid
-   */
-  convert(mode: GameMode, mods?: Object | null): void;
-  /**
-   * Check whether hitobjects appear too suspicious for further calculation.
-   *
-   * Sometimes a beatmap isn't created for gameplay but rather to test
-   * the limits of osu! itself. Difficulty- and/or performance calculation
-   * should likely be avoided on these maps due to potential performance
-   * issues.
-   */
-  isSuspicious(): boolean;
-  readonly bpm: number;
-  readonly mode: GameMode;
-  readonly nBreaks: number;
-  readonly nObjects: number;
-  readonly nCircles: number;
-  readonly nSliders: number;
+* Limits for a calculation.
+*
+* If a limit is exceeded, the calculation throws a
+* `CalculationAbortedError`.
+*/
+export interface CalculationBudget {
+    /**
+    * The maximum amount of hitobjects of the beatmap.
+    */
+    maxObjects?: number;
+    /**
+    * The maximum amount of slider segments of all sliders combined.
+    *
+    * The segments of a slider are its control points as well as its
+    * estimated head, ticks, repeats, and tail. The estimation is based on
+    * the slider's length so that the limit is checked before any slider
+    * path is computed.
+    */
+    maxSliderSegments?: number;
+    /**
+    * Timestamp in ms, as returned by `Date.now()`, after which the
+    * calculation is aborted.
+    *
+    * The deadline is checked after every few hitobjects. Preparing the
+    * beatmap, e.g. computing slider paths, cannot be interrupted so
+    * `maxObjects` and `maxSliderSegments` should be specified as well.
+    */
+    deadline?: number;
+    /**
+    * Signal to cancel the calculation.
+    *
+    * Only asynchronous calculations can observe the signal while they are
+    * running; synchronous ones only check it before starting.
+    */
+    signal?: AbortSignal;
+}
+
+/**
+* The error that is thrown when a calculation exceeds its
+* `CalculationBudget` or is cancelled.
+*/
+export interface CalculationAbortedError extends Error {
+    name: "CalculationAbortedError";
+    /**
+    * The limit that was exceeded or `"cancelled"` if the signal was aborted.
+    */
+    reason: "maxObjects" | "maxSliderSegments" | "deadline" | "cancelled";
+}
+
+/**
+* Timing-related info of a control point i.e. an uninherited timing point.
+*/
+export interface TimingPoint {
+    /**
+    * The start time in milliseconds.
+    */
+    time: number;
+    /**
+    * The duration of a beat in milliseconds.
+    */
+    beatLen: number;
+    /**
+    * The beats per minute.
+    */
+    bpm: number;
+}
+
+/**
+* Difficulty-related info of a control point.
+*/
+export interface DifficultyPoint {
+    /**
+    * The start time in milliseconds.
+    */
+    time: number;
+    /**
+    * The slider velocity multiplier.
+    */
+    sliderVelocity: number;
+    /**
+    * The beat length multiplier of slider ticks.
+    */
+    bpmMultiplier: number;
+    /**
+    * Whether slider ticks should be generated.
+    */
+    generateTicks: boolean;
+}
+
+/**
+* Effect-related info of a control point.
+*/
+export interface EffectPoint {
+    /**
+    * The start time in milliseconds.
+    */
+    time: number;
+    /**
+    * Whether kiai time is active.
+    */
+    kiai: boolean;
+    /**
+    * The scroll speed multiplier.
+    */
+    scrollSpeed: number;
+}
+
+/**
+* Sample-related info of a control point.
+*/
+export interface SamplePoint {
+    /**
+    * The start time in milliseconds.
+    */
+    time: number;
+    /**
+    * The sample bank i.e. `0` for none, `1` for normal, `2` for soft, and `3`
+    * for drum.
+    */
+    sampleBank: number;
+    /**
+    * The sample volume in percent.
+    */
+    sampleVolume: number;
+    /**
+    * The index of the custom sample bank; `0` for the default samples.
+    */
+    customSampleBank: number;
+}
+
+/**
+* The result of `GradualDifficulty.series`.
+*
+* Each array contains one value per included hitobject in the same order.
+* Skill values are only available for the mode they belong to.
+*
+* There are no skill values for osu!catch and osu!mania because their
+* difficulty attributes only contain the star rating, which is derived from
+* a single skill.
+*/
+export interface GradualDifficultySeries {
+    /**
+    * The start time of the hitobject in milliseconds.
+    *
+    * The time is the beatmap's time, i.e. it is not adjusted by the clock
+    * rate.
+    */
+    time: Float64Array;
+    /**
+    * The amount of processed hitobjects, i.e. `1` for the first hitobject.
+    */
+    position: Uint32Array;
+    /**
+    * The final star rating.
+    */
+    stars: Float64Array;
+    /**
+    * The difficulty of the aim skill.
+    *
+    * Only available for osu!standard.
+    */
+    aim?: Float64Array;
+    /**
+    * The difficulty of the speed skill.
+    *
+    * Only available for osu!standard.
+    */
+    speed?: Float64Array;
+    /**
+    * The difficulty of the flashlight skill.
+    *
+    * Only available for osu!standard.
+    */
+    flashlight?: Float64Array;
+    /**
+    * The difficulty of the stamina skill.
+    *
+    * Only available for osu!taiko.
+    */
+    stamina?: Float64Array;
+    /**
+    * The difficulty of the rhythm skill.
+    *
+    * Only available for osu!taiko.
+    */
+    rhythm?: Float64Array;
+    /**
+    * The difficulty of the color skill.
+    *
+    * Only available for osu!taiko.
+    */
+    color?: Float64Array;
+    /**
+    * The difficulty of the reading skill.
+    *
+    * Only available for osu!taiko.
+    */
+    reading?: Float64Array;
+}
+
+/**
+* A hitobject of a beatmap.
+*/
+export interface HitObject {
+    /**
+    * The kind of hitobject.
+    */
+    kind: HitObjectKind;
+    /**
+    * The x-coordinate of the hitobject.
+    */
+    x: number;
+    /**
+    * The y-coordinate of the hitobject.
+    */
+    y: number;
+    /**
+    * The start time in milliseconds.
+    */
+    startTime: number;
+    /**
+    * The end time in milliseconds.
+    *
+    * Equal to `startTime` for circles.
+    */
+    endTime: number;
+    /**
+    * Hitsound bitflags i.e. `1` for normal, `2` for whistle, `4` for finish,
+    * and `8` for clap.
+    */
+    hitSound: number;
+    /**
+    * The amount of repeats.
+    *
+    * Only available for sliders.
+    */
+    repeats?: number;
+    /**
+    * The expected length of the slider path in osu!pixels as specified in
+    * the `.osu` file.
+    *
+    * Only available for sliders.
+    */
+    pixelLength?: number;
+    /**
+    * The control points of the slider path, relative to the slider's head.
+    *
+    * Only available for sliders.
+    */
+    controlPoints?: SliderControlPoint[];
+    /**
+    * Hitsound bitflags for each node i.e. head, repeats, and tail.
+    *
+    * Only available for sliders.
+    */
+    nodeSounds?: number[];
+    /**
+    * Custom sample info as specified in the `.osu` file.
+    *
+    * Unavailable if the hitobjects no longer correspond to the `.osu` file,
+    * e.g. after converting to mania.
+    */
+    samples?: HitSamples;
+}
+
+/**
+* Custom sample info of a hitobject.
+*
+* Sample sets are `0` to use the active sample point's set, `1` for normal,
+* `2` for soft, and `3` for drum.
+*/
+export interface HitSamples {
+    /**
+    * The sample set of the normal sound.
+    */
+    normalSet: number;
+    /**
+    * The sample set of the whistle, finish, and clap sounds.
+    */
+    additionSet: number;
+    /**
+    * The custom sample index; `0` to use the active sample point's index.
+    */
+    index: number;
+    /**
+    * The sample volume; `0` to use the active sample point's volume.
+    */
+    volume: number;
+    /**
+    * The custom filename of the normal sound; empty if unspecified.
+    */
+    filename: string;
+}
+
+/**
+* A control point of a slider path.
+*/
+export interface SliderControlPoint {
+    /**
+    * The x-coordinate relative to the slider's head.
+    */
+    x: number;
+    /**
+    * The y-coordinate relative to the slider's head.
+    */
+    y: number;
+    /**
+    * The path type starting at this point if it starts a new segment, i.e.
+    * `"B"` or `"B<degree>"` for bezier, `"C"` for catmull, `"L"` for linear,
+    * and `"P"` for perfect curves.
+    */
+    pathType?: string;
+}
+
+/**
+* The result of `Performance.calculateScenarios`.
+*/
+export interface PerformanceScenarios {
+    /**
+    * The performance attributes of the score itself.
+    */
+    current: PerformanceAttributes;
+    /**
+    * The performance attributes of the score if it were a full combo, i.e.
+    * misses are considered to be 300s, all slider ticks and ends are hit,
+    * and combo is the maximum combo.
+    */
+    fc: PerformanceAttributes;
+    /**
+    * The pp of a full combo for each given accuracy in the same order.
+    */
+    accuracies: Float64Array;
+}
+
+/**
+* The content of a `.osr` file as bytes.
+*/
+export type ReplayContent = Uint8Array;
+
+/**
+* Arguments to provide the `Difficulty` constructor.
+*/
+export interface ScoreState {
+    /**
+    * Maximum combo that the score has had so far. **Not** the maximum
+    * possible combo of the map so far.
+    *
+    * Note that for osu!catch only fruits and droplets are considered for
+    * combo.
+    *
+    * Irrelevant for osu!mania.
+    */
+    maxCombo?: number;
+
+    /**
+    * "Large tick" hits for osu!standard.
+    *
+    * The meaning depends on the kind of score:
+    * - if set on osu!stable, this field is irrelevant and can be `0`
+    * - if set on osu!lazer *without* `CL`, this field is the amount of hit
+    *   slider ticks and repeats
+    * - if set on osu!lazer *with* `CL`, this field is the amount of hit
+    *   slider heads, ticks, and repeats
+    */
+    osuLargeTickHits?: number;
+
+    /**
+    * "Small tick" hits for osu!standard.
+    *
+    * These are essentially the slider end hits for lazer scores without
+    * slider accuracy.
+    *
+    * Only relevant for osu!lazer.
+    */ 
+    osuSmallTickHits?: number;
+
+    /**
+    * Amount of successfully hit slider ends.
+    *
+    * Only relevant for osu!standard in lazer.
+    */
+    sliderEndHits?: number;
+    
+    /**
+    * Amount of current gekis (n320 for osu!mania).
+    */
+    nGeki?: number;
+    /**
+    * Amount of current katus (tiny droplet misses for osu!catch / n200 for
+    * osu!mania).
+    */
+    nKatu?: number;
+    /**
+    * Amount of current 300s (fruits for osu!catch).
+    */
+    n300?: number;
+    /**
+    * Amount of current 100s (droplets for osu!catch).
+    */
+    n100?: number;
+    /**
+    * Amount of current 50s (tiny droplets for osu!catch).
+    */
+    n50?: number;
+    /**
+    * Amount of current misses (fruits + droplets for osu!catch).
+    */
+    misses?: number;
+}
+
+/**
+* The result of `ScoreStateUtils.fromAccuracy`.
+*/
+export interface StateFromAccuracy {
+    /**
+    * The generated score state.
+    */
+    state: ScoreState;
+    /**
+    * The accuracy between `0.0` and `100.0` that the generated score state
+    * achieves.
+    */
+    accuracy: number;
+}
+
+/**
+* The result of `Performance.solveForPp`.
+*/
+export interface PpSolution {
+    /**
+    * Whether the target pp can be reached at all.
+    */
+    reachable: boolean;
+    /**
+    * The least demanding value that reaches the target pp, i.e. the minimum
+    * accuracy or combo, or the maximum amount of misses.
+    *
+    * Only available if the target is reachable.
+    */
+    value?: number;
+    /**
+    * The pp for `value` if the target is reachable; otherwise the highest
+    * pp that can be achieved by varying the value.
+    */
+    pp: number;
+}
+
+/**
+* The result of `Strains.resample`.
+*
+* Each array contains one value per bucket. Skill series are only available
+* for the mode they belong to.
+*/
+export interface ResampledStrains {
+    /**
+    * The strains' gamemode.
+    */
+    mode: GameMode;
+    /**
+    * Length of a bucket in ms.
+    */
+    bucketLength: number;
+    /**
+    * The center of each bucket in ms, relative to the start of the first
+    * strain section.
+    */
+    time: Float64Array;
+    /**
+    * The combined series of all skills.
+    */
+    overall: Float64Array;
+    aim?: Float64Array;
+    aimNoSliders?: Float64Array;
+    speed?: Float64Array;
+    flashlight?: Float64Array;
+    color?: Float64Array;
+    reading?: Float64Array;
+    rhythm?: Float64Array;
+    stamina?: Float64Array;
+    singleColorStamina?: Float64Array;
+    movement?: Float64Array;
+    strains?: Float64Array;
+}
+
+/**
+* The result of validating hitresults and combo against a beatmap.
+*/
+export interface ScoreValidation {
+    /**
+    * Whether there are no errors. Warnings do not invalidate a score.
+    */
+    valid: boolean;
+    /**
+    * Values that are impossible for the beatmap.
+    */
+    errors: ValidationIssue[];
+    /**
+    * Values that are possible but suspicious or ignored.
+    */
+    warnings: ValidationIssue[];
+}
+
+/**
+* A single problem found during validation.
+*/
+export interface ValidationIssue {
+    /**
+    * The name of the field that the issue refers to.
+    *
+    * Issues about the sum of multiple hitresults use `totalHits` or, for
+    * tiny droplets in osu!catch, `totalTinyDroplets`.
+    */
+    field: string;
+    /**
+    * The value of the field.
+    */
+    value: number;
+    /**
+    * The maximum possible value for the field, if there is one.
+    */
+    max?: number;
+    /**
+    * Human-readable description of the issue.
+    */
+    message: string;
+}
+
+/**
+ * All beatmap data that is relevant for difficulty and performance
+ * calculation.
+ *
+ * It is recommended to call the method `Beatmap.free` on instances that are
+ * no longer in use to avoid the risk of leaking memory.
+ */
+export class Beatmap {
+  free(): void;
+  /**
+   * Create a new beatmap instance by parsing an `.osu` file's content.
+   * @throws Throws an error if decoding the map failed
+   */
+  constructor(args: BeatmapContent);
+  /**
+   * Convert a beatmap to a specific mode.
+   * @throws Throws an error if conversion fails or mods are invalid
+   */
+  convert(mode: GameMode, mods?: Object | null): void;
+  /**
+   * Encode the beatmap into the content of a `.osu` file of format
+   * version 14.
+   *
+   * Changes through `Beatmap.convert` are included but since `.osu` files
+   * cannot mark a beatmap as converted, decoding the result will yield
+   * `isConvert = false`. New combos and custom sample info of hitobjects
+   * are kept, although custom sample indices and volumes are moved into
+   * sample points unless the beatmap is osu!mania. Data that is not
+   * retained on decoding such as combo colors or storyboard events is
+   * omitted.
+   * @throws Throws an error if encoding fails
+   */
+  toOsuString(): string;
+  /**
+   * Encode the beatmap into the content of a `.osu` file of format
+   * version 14 as bytes.
+   *
+   * See `Beatmap.toOsuString`.
+   * @throws Throws an error if encoding fails
+   */
+  toBytes(): Uint8Array;
+  /**
+   * Create a new beatmap that only contains the hitobjects whose start time
+   * in milliseconds is within `startTime` (inclusive) and `endTime`
+   * (exclusive).
+   *
+   * Breaks and control points are trimmed to the range. Control points
+   * that are active at `startTime` are retained and hitobjects are not
+   * shifted in time.
+   */
+  slice(start_time: number, end_time: number): Beatmap;
+  /**
+   * Create a new beatmap that only contains the hitobjects with an index
+   * between `start` (inclusive) and `end` (exclusive).
+   *
+   * Breaks and control points are trimmed to the time range of the
+   * hitobjects, see `Beatmap.slice`.
+   */
+  sliceObjects(start: number, end: number): Beatmap;
+  /**
+   * Check whether hitobjects appear too suspicious for further calculation.
+   *
+   * Sometimes a beatmap isn't created for gameplay but rather to test
+   * the limits of osu! itself. Difficulty- and/or performance calculation
+   * should likely be avoided on these maps due to potential performance
+   * issues.
+   *
+   * See `Beatmap.checkSuspicion` for the reason why a beatmap is
+   * suspicious.
+   */
+  isSuspicious(): boolean;
+  /**
+   * Check whether hitobjects appear too suspicious for further calculation
+   * and if so, report why.
+   *
+   * Returns `undefined` if the beatmap is not suspicious. Otherwise, the
+   * report contains the kind of suspicion as well as the offending values
+   * and hitobjects which can help deciding whether to skip or throttle
+   * calculations on the beatmap.
+   */
+  checkSuspicion(): SuspicionReport | undefined;
+  readonly bpm: number;
+  /**
+   * The online ID of the beatmap; `-1` if unavailable.
+   */
+  readonly beatmapId: number;
+  /**
+   * The online ID of the beatmap's mapset; `0` if unavailable.
+   */
+  readonly beatmapSetId: number;
+  /**
+   * The filename of the audio file.
+   */
+  readonly audioFile: string;
+  /**
+   * The start time of the song preview in milliseconds; `-1` if
+   * unspecified.
+   */
+  readonly previewTime: number;
+  /**
+   * The filename of the background image.
+   */
+  readonly backgroundFile: string;
+  /**
+   * The beats per minute of the timing point that lasts the longest.
+   *
+   * Same as `bpm`.
+   */
+  readonly mostCommonBpm: number;
+  /**
+   * The lowest beats per minute across all timing points.
+   */
+  readonly minBpm: number;
+  /**
+   * The highest beats per minute across all timing points.
+   */
+  readonly maxBpm: number;
+  /**
+   * The beats per minute at the given time in milliseconds.
+   */
+  bpmAt(time: number): number;
+  readonly mode: GameMode;
+  readonly nBreaks: number;
+  readonly nObjects: number;
+  readonly nCircles: number;
+  readonly nSliders: number;
   readonly nSpinners: number;
   readonly nHolds: number;
+  /**
+   * All hitobjects of the beatmap.
+   *
+   * For large maps, prefer the typed array methods like
+   * `hitObjectStartTimes` which avoid creating an object per hitobject.
+   */
+  hitObjects(): HitObject[];
+  /**
+   * The hitobject at the given index.
+   */
+  hitObjectAt(idx: number): HitObject | undefined;
+  /**
+   * The kind of each hitobject.
+   */
+  hitObjectKinds(): Uint8Array;
+  /**
+   * The start time of each hitobject.
+   */
+  hitObjectStartTimes(): Float64Array;
+  /**
+   * The end time of each hitobject.
+   */
+  hitObjectEndTimes(): Float64Array;
+  /**
+   * The position of each hitobject, interleaved as `[x0, y0, x1, y1, ...]`.
+   */
+  hitObjectPositions(): Float32Array;
+  /**
+   * The hitsound bitflags of each hitobject.
+   */
+  hitObjectHitSounds(): Uint8Array;
+  /**
+   * All uninherited timing points of the beatmap.
+   */
+  timingPoints(): TimingPoint[];
+  /**
+   * All difficulty points of the beatmap, including slider velocity changes.
+   */
+  difficultyPoints(): DifficultyPoint[];
+  /**
+   * All effect points of the beatmap, including kiai sections.
+   */
+  effectPoints(): EffectPoint[];
+  /**
+   * All sample points of the beatmap.
+   */
+  samplePoints(): SamplePoint[];
+  readonly title: string;
+  readonly titleUnicode: string;
+  readonly artist: string;
+  readonly artistUnicode: string;
+  /**
+   * r" The name of the beatmap's mapper.
+   */
+  readonly creator: string;
+  /**
+   * r" The name of the difficulty.
+   * r"
+   * r" Not to be confused with `version` which is the `.osu` file format
+   * r" version.
+   */
+  readonly difficultyName: string;
+  readonly source: string;
+  /**
+   * r" Space-separated search tags.
+   */
+  readonly tags: string;
   readonly version: number;
   readonly isConvert: boolean;
   readonly stackLeniency: number;
@@ -375,14 +1404,6 @@ export class Beatmap {
 }
 export class BeatmapAttributes {
   private constructor();
-/**
-** Return copy of self without private attributes.
-*/
-  toJSON(): Object;
-/**
-* Return stringified version of self.
-*/
-  toString(): string;
   free(): void;
   /**
    * The approach rate.
@@ -427,6 +1448,22 @@ export class BeatmapAttributes {
    * Only available for osu!.
    */
   readonly odMehHitWindow: number | undefined;
+  /**
+   * Convert the attributes into a plain object that includes a schema
+   * version.
+   *
+   * Unavailable attributes are omitted.
+   */
+  toJSON(): Object;
+  /**
+   * Stringified version of `BeatmapAttributes.toJSON`.
+   */
+  toString(): string;
+  /**
+   * Create attributes from the result of `BeatmapAttributes.toJSON`.
+   * @throws Throws an error if the JSON is invalid or of an unsupported schema version
+   */
+  static fromJSON(json: Object): BeatmapAttributes;
 }
 export class BeatmapAttributesBuilder {
   free(): void;
@@ -463,15 +1500,87 @@ export class Difficulty {
   constructor(args?: DifficultyArgs | null);
   /**
    * Perform the difficulty calculation.
+   *
+   * If a `DifficultyCache` was specified, previously calculated attributes
+   * for the same beatmap and settings are reused.
    */
   calculate(map: Beatmap): DifficultyAttributes;
+  /**
+   * Perform the difficulty calculation within the given budget.
+   *
+   * Hitobjects are processed in chunks and the budget is checked
+   * inbetween. The resulting attributes are the same as for `calculate`.
+   *
+   * If a `DifficultyCache` was specified, previously calculated attributes
+   * for the same beatmap and settings are reused without checking the
+   * budget.
+   * @throws Throws a `CalculationAbortedError` if the budget is exceeded or an error if the budget is invalid
+   */
+  calculateWithBudget(map: Beatmap, budget: CalculationBudget): DifficultyAttributes;
+  /**
+   * Same as `calculateWithBudget` but the calculation yields to the event
+   * loop after every few hitobjects so that it does not block other tasks
+   * and can be cancelled through the budget's `signal`.
+   *
+   * Preparing the beatmap, e.g. computing slider paths, happens before the
+   * first yield.
+   *
+   * The promise rejects with a `CalculationAbortedError` if the budget is
+   * exceeded.
+   * @throws Throws an error if the budget is invalid
+   */
+  calculateAsync(map: Beatmap, budget?: CalculationBudget | null): Promise<DifficultyAttributes>;
+  /**
+   * Perform the difficulty calculation for multiple sets of arguments on
+   * the same beatmap.
+   *
+   * Returns the attributes in the same order as the given arguments.
+   * Arguments with the same settings, e.g. an unset clock rate and the
+   * clock rate of the mods, are only calculated once. Beyond that,
+   * calculations do not share any work.
+   *
+   * If the `threads` feature is enabled and a thread pool was initialized
+   * through `initThreadPool`, the calculations are performed in parallel.
+   * @throws Throws an error if the arguments are invalid
+   */
+  static calculateMany(map: Beatmap, args: DifficultyArgs[]): DifficultyAttributes[];
+  /**
+   * Perform the difficulty calculation for multiple sets of arguments on
+   * each of the given beatmaps.
+   *
+   * Returns a list for each beatmap in the same order as the beatmaps,
+   * each containing the attributes in the same order as the given
+   * arguments. If no arguments are given, the default settings are used.
+   * Identical beatmaps and arguments with the same settings are only
+   * calculated once.
+   *
+   * If the `threads` feature is enabled and a thread pool was initialized
+   * through `initThreadPool`, the calculations of all beatmaps are
+   * performed in parallel.
+   * @throws Throws an error if the beatmaps or arguments are invalid
+   */
+  static calculateBatch(maps: Beatmap[], args?: DifficultyArgs[] | null): DifficultyAttributes[][];
   /**
    * Perform the difficulty calculation but instead of evaluating strain
    * values, return them as is.
    *
    * Suitable to plot the difficulty over time.
+   *
+   * If `perObject` is specified, the strains of each hitobject are
+   * included alongside their start time. If `sectionTimes` is specified,
+   * the start time of each strain section is included.
+   * @throws Throws an error if the options are invalid
    */
-  strains(map: Beatmap): Strains;
+  strains(map: Beatmap, options?: StrainsArgs | null): Strains;
+  /**
+   * Find the hardest sections of each skill as well as how concentrated
+   * the difficulty is.
+   *
+   * Sections are formed by consecutive strain sections so the same
+   * caveats as for `strains` apply.
+   * @throws Throws an error if the options are invalid
+   */
+  analyzeStrains(map: Beatmap, options?: StrainAnalysisArgs | null): StrainAnalysis;
   /**
    * Returns a gradual difficulty calculator for the current difficulty settings.
    */
@@ -493,20 +1602,13 @@ export class Difficulty {
   set odWithMods(value: boolean | null | undefined);
   set passedObjects(value: number | null | undefined);
   set hardrockOffsets(value: boolean | null | undefined);
+  set cache(value: DifficultyCache | null | undefined);
 }
 /**
  * The result of a difficulty calculation.
  */
 export class DifficultyAttributes {
   private constructor();
-/**
-** Return copy of self without private attributes.
-*/
-  toJSON(): Object;
-/**
-* Return stringified version of self.
-*/
-  toString(): string;
   free(): void;
   /**
    * The attributes' gamemode.
@@ -671,32 +1773,96 @@ export class DifficultyAttributes {
    *
    * Only available for osu! and osu!taiko.
    */
-  readonly greatHitWindow: number | undefined;
+  readonly greatHitWindow: number | undefined;
+  /**
+   * The perceived hit window for an n100 inclusive of rate-adjusting mods
+   * (DT/HT/etc)
+   *
+   * Only available for osu! and osu!taiko.
+   */
+  readonly okHitWindow: number | undefined;
+  /**
+   * The perceived hit window for an n50 inclusive of rate-adjusting mods
+   * (DT/HT/etc)
+   *
+   * Only available for osu!.
+   */
+  readonly mehHitWindow: number | undefined;
+  /**
+   * The ratio of stamina difficulty from mono-color (single color) streams to total
+   * stamina difficulty.
+   *
+   * Only available for osu!taiko.
+   */
+  readonly monoStaminaFactor: number | undefined;
+  /**
+   * Return the maximum combo.
+   */
+  readonly maxCombo: number;
+  /**
+   * Encode the attributes into a compact binary format.
+   *
+   * The bytes start with a format version and the mode, followed by the
+   * mode's attributes. Decoding them via `DifficultyAttributes.fromBytes`
+   * yields the exact same attributes.
+   * @throws Throws an error if the attributes are invalid
+   */
+  toBytes(): Uint8Array;
+  /**
+   * Decode attributes from bytes that were created through
+   * `DifficultyAttributes.toBytes`.
+   * @throws Throws an error if the bytes are invalid or of an unsupported version
+   */
+  static fromBytes(bytes: Uint8Array): DifficultyAttributes;
+  /**
+   * Convert the attributes into a plain object that includes a schema
+   * version.
+   *
+   * Unavailable attributes are omitted.
+   */
+  toJSON(): Object;
+  /**
+   * Stringified version of `DifficultyAttributes.toJSON`.
+   */
+  toString(): string;
   /**
-   * The perceived hit window for an n100 inclusive of rate-adjusting mods
-   * (DT/HT/etc)
-   *
-   * Only available for osu! and osu!taiko.
+   * Create attributes from the result of `DifficultyAttributes.toJSON`.
+   * @throws Throws an error if the JSON is invalid or of an unsupported schema version
    */
-  readonly okHitWindow: number | undefined;
+  static fromJSON(json: Object): DifficultyAttributes;
+}
+/**
+ * Cache for difficulty attributes.
+ *
+ * Attributes are stored for the content of a beatmap alongside the
+ * difficulty settings like mods or clock rate that they were calculated
+ * with. Once the capacity is reached, the least recently used attributes are
+ * discarded.
+ *
+ * To use the cache, pass it via the `cache` argument to `Difficulty` or
+ * `Performance`.
+ */
+export class DifficultyCache {
+  free(): void;
   /**
-   * The perceived hit window for an n50 inclusive of rate-adjusting mods
-   * (DT/HT/etc)
+   * Create a new cache that stores attributes of up to `capacity`
+   * calculations.
    *
-   * Only available for osu!.
+   * Defaults to a capacity of 128.
    */
-  readonly mehHitWindow: number | undefined;
+  constructor(capacity?: number | null);
   /**
-   * The ratio of stamina difficulty from mono-color (single color) streams to total
-   * stamina difficulty.
-   *
-   * Only available for osu!taiko.
+   * Remove all stored attributes.
    */
-  readonly monoStaminaFactor: number | undefined;
+  clear(): void;
   /**
-   * Return the maximum combo.
+   * The amount of stored attributes.
    */
-  readonly maxCombo: number;
+  readonly size: number;
+  /**
+   * The maximum amount of stored attributes.
+   */
+  readonly capacity: number;
 }
 /**
  * Gradually calculate difficulty attributes after each hitobject.
@@ -720,6 +1886,49 @@ export class GradualDifficulty {
    * into a list and return them.
    */
   collect(): DifficultyAttributes[];
+  /**
+   * Move to the given amount of processed hitobjects and return the
+   * attributes after the last of them.
+   *
+   * The position may also lie before the current one. Attributes of
+   * previously processed hitobjects are stored so seeking backwards does
+   * not require recalculation.
+   *
+   * Returns `undefined` for position `0`.
+   */
+  seek(position: number): DifficultyAttributes | undefined;
+  /**
+   * Move to the last hitobject that starts at or before the given time in
+   * milliseconds and return the attributes after it.
+   *
+   * The time is the beatmap's time, i.e. it is not adjusted by the clock
+   * rate. Same as for `seek`, the time may lie before the current one.
+   *
+   * Returns `undefined` if no hitobject starts at or before that time.
+   * @throws Throws an error if the hitobjects' start times cannot be aligned with the gradual calculation
+   */
+  advanceTo(time: number): DifficultyAttributes | undefined;
+  /**
+   * Create a copy of this calculator at the current position.
+   *
+   * Both calculators share their calculated attributes so cloning is cheap
+   * and can be used to snapshot a position.
+   */
+  clone(): GradualDifficulty;
+  /**
+   * Calculate the attributes after every hitobject and return them as
+   * columns of typed arrays.
+   *
+   * If a stride is given, only every `stride`th hitobject is included, as
+   * well as the last one. The position of this calculator is not
+   * modified.
+   * @throws Throws an error if the stride is zero or if the hitobjects' start times cannot be aligned with the gradual calculation
+   */
+  series(stride?: number | null): GradualDifficultySeries;
+  /**
+   * The amount of hitobjects that have been processed.
+   */
+  readonly position: number;
   /**
    * Returns the amount of remaining items.
    */
@@ -744,11 +1953,143 @@ export class GradualPerformance {
    * `n=1` will process 2, and so on.
    */
   nth(state: ScoreState, n: number): PerformanceAttributes | undefined;
+  /**
+   * Process the next hit object with the given judgement and calculate the
+   * performance attributes for the accumulated score state.
+   *
+   * The score state is accumulated internally so, unlike for `next`, only
+   * the current hit object's judgement is required. Hit events and score
+   * states can be mixed; passing a score state to `next` or `nth`
+   * replaces the accumulated state.
+   * @throws Throws an error if the hit event is invalid
+   */
+  nextHit(hit: HitEvent): PerformanceAttributes | undefined;
+  /**
+   * Process a hit object for each hit event and return the pp after each
+   * of them.
+   *
+   * Stops early if there are no remaining hit objects so the returned list
+   * may be shorter than the given one.
+   * @throws Throws an error if the hit events are invalid
+   */
+  nextHits(hits: HitEvent[]): Float64Array;
+  /**
+   * Move to the given amount of processed hitobjects and calculate the
+   * performance attributes for the given score state.
+   *
+   * The position may also lie before the current one. Difficulty
+   * attributes of previously processed hitobjects are stored so seeking
+   * backwards does not require recalculation.
+   *
+   * Returns `undefined` for position `0`.
+   * @throws Throws an error if the score state is invalid
+   */
+  seek(position: number, state: ScoreState): PerformanceAttributes | undefined;
+  /**
+   * Move to the last hitobject that starts at or before the given time in
+   * milliseconds and calculate the performance attributes for the given
+   * score state.
+   *
+   * The time is the beatmap's time, i.e. it is not adjusted by the clock
+   * rate. Same as for `seek`, the time may lie before the current one.
+   *
+   * Returns `undefined` if no hitobject starts at or before that time.
+   * @throws Throws an error if the score state is invalid or if the hitobjects' start times cannot be aligned with the gradual calculation
+   */
+  advanceTo(time: number, state: ScoreState): PerformanceAttributes | undefined;
+  /**
+   * Create a copy of this calculator at the current position, including
+   * the accumulated score state.
+   *
+   * Both calculators share their calculated difficulty attributes so
+   * cloning is cheap and can be used to snapshot a position.
+   */
+  clone(): GradualPerformance;
+  /**
+   * The amount of hitobjects that have been processed.
+   */
+  readonly position: number;
+  /**
+   * The score state that was accumulated so far.
+   */
+  readonly state: ScoreState;
   /**
    * Returns the amount of remaining items.
    */
   readonly nRemaining: number;
 }
+/**
+ * Strains of each hitobject alongside their start time.
+ *
+ * Only the strain peaks of sections are available so the strain of a
+ * hitobject is the peak of the section that contains it.
+ *
+ * For osu!taiko only circles and for osu!catch only fruits and droplets are
+ * considered hitobjects.
+ */
+export class ObjectStrains {
+  private constructor();
+/**
+** Return copy of self without private attributes.
+*/
+  toJSON(): Object;
+/**
+* Return stringified version of self.
+*/
+  toString(): string;
+  free(): void;
+  /**
+   * Strains of the aim skill in osu!.
+   */
+  readonly aim: Float64Array | undefined;
+  /**
+   * Strains of the aim skill without sliders in osu!.
+   */
+  readonly aimNoSliders: Float64Array | undefined;
+  /**
+   * Strains of the speed skill in osu!.
+   */
+  readonly speed: Float64Array | undefined;
+  /**
+   * Strains of the flashlight skill in osu!.
+   */
+  readonly flashlight: Float64Array | undefined;
+  /**
+   * Strains of the color skill in osu!taiko.
+   */
+  readonly color: Float64Array | undefined;
+  /**
+   * Strains of the reading skill in osu!taiko.
+   */
+  readonly reading: Float64Array | undefined;
+  /**
+   * Strains of the rhythm skill in osu!taiko.
+   */
+  readonly rhythm: Float64Array | undefined;
+  /**
+   * Strains of the stamina skill in osu!taiko.
+   */
+  readonly stamina: Float64Array | undefined;
+  /**
+   * Strains of the single color stamina skill in osu!taiko.
+   */
+  readonly singleColorStamina: Float64Array | undefined;
+  /**
+   * Strains of the movement skill in osu!catch.
+   */
+  readonly movement: Float64Array | undefined;
+  /**
+   * Strains of the strain skill in osu!mania.
+   */
+  readonly strains: Float64Array | undefined;
+  /**
+   * The start time of each hitobject in ms.
+   *
+   * Unlike `Strains.sectionLength`, the time is not adjusted by the clock
+   * rate.
+   */
+  readonly time: Float64Array;
+}
 /**
  * Builder for a performance calculation.
  */
@@ -769,8 +2110,96 @@ export class Performance {
    * However, be careful that the passed attributes have been calculated
    * for the same difficulty settings like mods, clock rate, beatmap,
    * custom ar, ... otherwise the final attributes will be incorrect.
+   *
+   * If a beatmap is passed and a `DifficultyCache` was specified,
+   * previously calculated attributes for the same beatmap and settings are
+   * reused.
    */
   calculate(args: MapOrAttributes): PerformanceAttributes;
+  /**
+   * Calculate performance attributes on a beatmap while calculating its
+   * difficulty attributes within the given budget.
+   *
+   * See `Difficulty.calculateWithBudget`.
+   * @throws Throws a `CalculationAbortedError` if the budget is exceeded or an error if the arguments are invalid
+   */
+  calculateWithBudget(map: Beatmap, budget: CalculationBudget): PerformanceAttributes;
+  /**
+   * Same as `calculateWithBudget` but the difficulty calculation yields to
+   * the event loop after every few hitobjects.
+   *
+   * See `Difficulty.calculateAsync`.
+   * @throws Throws an error if the arguments are invalid
+   */
+  calculateAsync(map: Beatmap, budget?: CalculationBudget | null): Promise<PerformanceAttributes>;
+  /**
+   * Calculate performance attributes for a replay.
+   *
+   * Mods, hitresults, and combo are taken from the replay and override the
+   * corresponding arguments of this calculator. Other arguments like
+   * clock rate or custom ar remain in effect.
+   *
+   * The same caveats regarding passed attributes apply as for
+   * `Performance.calculate`.
+   */
+  calculateReplay(args: MapOrAttributes, replay: Replay): PerformanceAttributes;
+  /**
+   * Calculate performance attributes for multiple sets of arguments on the
+   * same beatmap or attributes.
+   *
+   * Returns the attributes in the same order as the given arguments.
+   *
+   * If a beatmap is passed, difficulty attributes are only calculated once
+   * for each distinct set of difficulty settings like mods or clock rate.
+   * With the `threads` feature, they are calculated in parallel.
+   * If attributes are passed, they are used for all arguments so the same
+   * caveats apply as for `Performance.calculate`.
+   * @throws Throws an error if the arguments are invalid
+   */
+  static calculateMany(args: MapOrAttributes, scores: PerformanceArgs[]): PerformanceAttributes[];
+  /**
+   * Same as `Performance.calculateMany` but only returns the pp values.
+   * @throws Throws an error if the arguments are invalid
+   */
+  static calculateManyPp(args: MapOrAttributes, scores: PerformanceArgs[]): Float64Array;
+  /**
+   * Calculate the performance of a score as well as the performance it
+   * would have as full combo and the performance of full combos with the
+   * given accuracies.
+   *
+   * Difficulty attributes are only calculated once for all scenarios.
+   * Hitresults, combo, and accuracy of this calculator are ignored in
+   * favor of the score state while all other arguments remain in effect.
+   *
+   * The same caveats regarding passed attributes apply as for
+   * `Performance.calculate`.
+   * @throws Throws an error if the arguments are invalid
+   */
+  calculateScenarios(args: MapOrAttributes, state: ScoreState, options?: PerformanceScenariosArgs | null): PerformanceScenarios;
+  /**
+   * Search for the least demanding accuracy, amount of misses, or combo
+   * that reaches the target pp.
+   *
+   * All other arguments of this calculator remain in effect. When varying
+   * accuracy, specified hitresults other than misses are ignored.
+   *
+   * Difficulty attributes are only calculated once and the same caveats
+   * regarding passed attributes apply as for `Performance.calculate`.
+   * @throws Throws an error if the arguments are invalid or the target pp
+   * is not a finite number
+   */
+  solveForPp(args: MapOrAttributes, target_pp: number, options?: SolveForPpArgs | null): PpSolution;
+  /**
+   * Check whether the specified hitresults and combo are possible on a
+   * beatmap.
+   *
+   * Only the values that were specified are validated. Errors and
+   * warnings are determined through the object counts of the difficulty
+   * attributes so the same caveats regarding passed attributes apply as
+   * for `Performance.calculate`.
+   * @throws Throws an error if the arguments are invalid
+   */
+  validate(args: MapOrAttributes): ScoreValidation;
   set mods(value: Object | null | undefined);
   set lazer(value: boolean | null | undefined);
   set clockRate(value: number | null | undefined);
@@ -784,6 +2213,7 @@ export class Performance {
   set odWithMods(value: boolean | null | undefined);
   set passedObjects(value: number | null | undefined);
   set hardrockOffsets(value: boolean | null | undefined);
+  set cache(value: DifficultyCache | null | undefined);
   set accuracy(value: number | null | undefined);
   set combo(value: number | null | undefined);
   set largeTickHits(value: number | null | undefined);
@@ -802,14 +2232,6 @@ export class Performance {
  */
 export class PerformanceAttributes {
   private constructor();
-/**
-** Return copy of self without private attributes.
-*/
-  toJSON(): Object;
-/**
-* Return stringified version of self.
-*/
-  toString(): string;
   free(): void;
   /**
    * The difficulty attributes.
@@ -873,6 +2295,174 @@ export class PerformanceAttributes {
    * Only available for osu!taiko and osu!mania.
    */
   readonly ppDifficulty: number | undefined;
+  /**
+   * Convert the attributes into a plain object that includes a schema
+   * version.
+   *
+   * Unavailable attributes are omitted.
+   */
+  toJSON(): Object;
+  /**
+   * Stringified version of `PerformanceAttributes.toJSON`.
+   */
+  toString(): string;
+  /**
+   * Create attributes from the result of `PerformanceAttributes.toJSON`.
+   * @throws Throws an error if the JSON is invalid or of an unsupported schema version
+   */
+  static fromJSON(json: Object): PerformanceAttributes;
+}
+/**
+ * A decoded `.osr` replay file.
+ *
+ * It is recommended to call the method `Replay.free` on instances that are
+ * no longer in use to avoid the risk of leaking memory.
+ */
+export class Replay {
+  free(): void;
+  /**
+   * Create a new replay instance by decoding an `.osr` file's content.
+   * @throws Throws an error if decoding the replay failed
+   */
+  constructor(args: ReplayContent);
+  /**
+   * MD5 hash of the played beatmap.
+   */
+  readonly beatmapHash: string;
+  readonly playerName: string;
+  /**
+   * MD5 hash of the replay.
+   */
+  readonly replayHash: string;
+  /**
+   * The life bar graph as comma-separated `time|life` pairs.
+   */
+  readonly lifeBar: string;
+  readonly mode: GameMode;
+  /**
+   * The legacy bitflags of the replay's mods.
+   */
+  readonly mods: number;
+  /**
+   * Whether the replay contains additional score info from osu!lazer.
+   */
+  readonly isLazer: boolean;
+  /**
+   * Windows ticks i.e. 100ns intervals since 0001-01-01 when the score was
+   * set.
+   */
+  readonly timestamp: number;
+  readonly onlineId: number;
+  /**
+   * The accuracy of a score with the `TargetPractice` mod.
+   */
+  readonly targetPracticeAccuracy: number | undefined;
+  /**
+   * The seed for osu!'s random number generator.
+   */
+  readonly rngSeed: number | undefined;
+  /**
+   * The amount of "large tick" hits.
+   *
+   * Only available for osu!lazer replays.
+   */
+  readonly largeTickHits: number | undefined;
+  /**
+   * The amount of "small tick" hits.
+   *
+   * Only available for osu!lazer replays.
+   */
+  readonly smallTickHits: number | undefined;
+  /**
+   * The amount of slider end hits.
+   *
+   * Only available for osu!lazer replays.
+   */
+  readonly sliderEndHits: number | undefined;
+  /**
+   * The amount of replay frames.
+   */
+  readonly nFrames: number;
+  /**
+   * The absolute time in milliseconds of each replay frame.
+   */
+  frameTimes(): Float64Array;
+  /**
+   * The x-coordinate of each replay frame.
+   */
+  frameX(): Float32Array;
+  /**
+   * The y-coordinate of each replay frame.
+   */
+  frameY(): Float32Array;
+  /**
+   * The pressed keys of each replay frame as bitflags.
+   */
+  frameKeys(): Uint32Array;
+  readonly version: number;
+  readonly n300: number;
+  readonly n100: number;
+  readonly n50: number;
+  readonly nGeki: number;
+  readonly nKatu: number;
+  readonly misses: number;
+  readonly score: number;
+  readonly maxCombo: number;
+  readonly perfect: boolean;
+}
+/**
+ * Functions that operate on a `ScoreState`.
+ *
+ * Since `ScoreState` is a plain object rather than a class, its functions
+ * are provided through this class.
+ */
+export class ScoreStateUtils {
+  private constructor();
+  free(): void;
+  /**
+   * Generate the score state that best matches the given accuracy without
+   * calculating performance.
+   *
+   * If a beatmap is passed as argument, difficulty attributes will have to
+   * be calculated internally which is a comparably expensive task. Hence,
+   * passing previously calculated attributes should be prefered whenever
+   * available.
+   * @throws Throws an error if the arguments are invalid
+   */
+  static fromAccuracy(args: MapOrAttributes, options?: StateFromAccuracyArgs | null): StateFromAccuracy;
+  /**
+   * Calculate the accuracy between `0.0` and `100.0` of a score state,
+   * defined the same way as in performance calculation.
+   *
+   * For osu!lazer scores in osu!, the maximum amount of slider-related
+   * hitresults is taken from the given `attributes`. If none are given,
+   * the state's own slider-related hits are considered to be the maximum.
+   * @throws Throws an error if the arguments are invalid
+   */
+  static accuracy(state: ScoreState, mode: GameMode, options?: StateAccuracyArgs | null): number;
+  /**
+   * Determine the grade that a score state would be awarded.
+   *
+   * osu!stable and osu!lazer grades differ; the former depend on the
+   * ratio of hitresults in osu! and osu!taiko while the latter depend on
+   * accuracy. The `HD` and `FL` mods, as well as `FI` in osu!mania, turn
+   * `SS` and `S` grades into their silver variants.
+   *
+   * See `ScoreStateUtils.accuracy` regarding the `attributes` argument.
+   * @throws Throws an error if the arguments are invalid
+   */
+  static grade(state: ScoreState, mode: GameMode, options?: StateAccuracyArgs | null): Grade;
+  /**
+   * Check whether the hitresults and combo of a score state are possible
+   * on a beatmap.
+   *
+   * Errors and warnings are determined through the object counts of the
+   * difficulty attributes. If a beatmap is passed as argument, difficulty
+   * attributes will have to be calculated internally which is a
+   * comparably expensive task.
+   * @throws Throws an error if the arguments are invalid
+   */
+  static validate(args: MapOrAttributes, state: ScoreState, options?: StateValidationArgs | null): ScoreValidation;
 }
 /**
  * The result of calculating the strains of a beatmap.
@@ -881,14 +2471,6 @@ export class PerformanceAttributes {
  */
 export class Strains {
   private constructor();
-/**
-** Return copy of self without private attributes.
-*/
-  toJSON(): Object;
-/**
-* Return stringified version of self.
-*/
-  toString(): string;
   free(): void;
   /**
    * The strains' gamemode.
@@ -942,4 +2524,50 @@ export class Strains {
    * Strain peaks of the strain skill in osu!mania.
    */
   readonly strains: Float64Array | undefined;
+  /**
+   * The start time of each strain section in ms.
+   *
+   * Unlike `sectionLength`, the time is not adjusted by the clock rate.
+   *
+   * Only available if `sectionTimes` was specified for
+   * `Difficulty.strains`.
+   */
+  readonly sectionTimes: Float64Array | undefined;
+  /**
+   * Strains of each hitobject.
+   *
+   * Only available if `perObject` was specified for `Difficulty.strains`.
+   */
+  readonly objects: ObjectStrains | undefined;
+  /**
+   * Convert the strains into a plain object that includes a schema
+   * version.
+   *
+   * Strain peaks of skills that are unavailable for the mode are omitted.
+   */
+  toJSON(): Object;
+  /**
+   * Stringified version of `Strains.toJSON`.
+   */
+  toString(): string;
+  /**
+   * Create strains from the result of `Strains.toJSON`.
+   * @throws Throws an error if the JSON is invalid or of an unsupported schema version
+   */
+  static fromJSON(json: Object): Strains;
+  /**
+   * Resample the strain peaks of all skills into the given amount of
+   * buckets of equal length and add a combined `overall` series.
+   *
+   * Each bucket holds the highest peak of the sections it overlaps with so
+   * that strains of different maps, modes, or mods can be charted
+   * alongside each other.
+   *
+   * The `overall` series is the sum of all skills except for
+   * `aimNoSliders` and `singleColorStamina` since those are variants of
+   * other skills. Smoothing and normalization are applied to each skill
+   * before they are summed up; the sum is normalized again afterwards.
+   * @throws Throws an error if `nBuckets` is zero or the options are invalid
+   */
+  resample(n_buckets: number, options?: StrainsResampleArgs | null): ResampledStrains;
 }
@@ -0,0 +1,249 @@
+use wasm_bindgen::{JsCast, JsValue, prelude::wasm_bindgen};
+
+use crate::{
+    args::difficulty::StrainAnalysisArgs,
+    strains::{JsStrains, SectionLayout},
+    util,
+};
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = StrainAnalysis)]
+    pub type JsStrainAnalysis;
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const _: &'static str = r#"/**
+* The result of `Difficulty.analyzeStrains`.
+*
+* Skill analyses are only available for the mode they belong to.
+*/
+export interface StrainAnalysis {
+    /**
+    * The strains' gamemode.
+    */
+    mode: GameMode;
+    aim?: SkillAnalysis;
+    aimNoSliders?: SkillAnalysis;
+    speed?: SkillAnalysis;
+    flashlight?: SkillAnalysis;
+    color?: SkillAnalysis;
+    reading?: SkillAnalysis;
+    rhythm?: SkillAnalysis;
+    stamina?: SkillAnalysis;
+    singleColorStamina?: SkillAnalysis;
+    movement?: SkillAnalysis;
+    strains?: SkillAnalysis;
+}
+
+/**
+* Where the difficulty of a skill lies within a beatmap.
+*/
+export interface SkillAnalysis {
+    /**
+    * How concentrated the difficulty is, i.e. the highest strain peak
+    * divided by the average strain peak.
+    *
+    * `1` means that the difficulty is spread evenly across the beatmap,
+    * higher values indicate spikes. `0` if there are no strains.
+    */
+    spikiness: number;
+    /**
+    * The hardest sections sorted by their average strain, hardest first.
+    *
+    * Sections do not overlap.
+    */
+    sections: HardestSection[];
+}
+
+/**
+* A section with particularly high strain.
+*/
+export interface HardestSection {
+    /**
+    * Start time of the section in ms.
+    *
+    * The time is the beatmap's time, i.e. it is not adjusted by the clock
+    * rate.
+    */
+    startTime: number;
+    /**
+    * End time of the section in ms.
+    */
+    endTime: number;
+    /**
+    * The highest strain peak within the section.
+    */
+    peak: number;
+    /**
+    * The average strain peak within the section.
+    */
+    average: number;
+    /**
+    * Indices of the hitobjects within the section.
+    *
+    * For osu!taiko only circles and for osu!catch only fruits and droplets
+    * are considered hitobjects.
+    */
+    objects: Uint32Array;
+}"#;
+
+const DEFAULT_COUNT: u32 = 3;
+const DEFAULT_WINDOW_LEN: f64 = 4000.0;
+
+/// Finds the hardest sections of each skill.
+pub struct StrainAnalysis<'a> {
+    strains: &'a JsStrains,
+    layout: SectionLayout,
+    /// The section index of each hitobject.
+    object_sections: Vec<usize>,
+    /// The amount of strain sections per window.
+    window_sections: usize,
+    count: usize,
+}
+
+impl<'a> StrainAnalysis<'a> {
+    pub fn new(
+        strains: &'a JsStrains,
+        start_times: &[f64],
+        layout: SectionLayout,
+        clock_rate: f64,
+        args: &StrainAnalysisArgs,
+    ) -> Self {
+        let object_sections = start_times
+            .iter()
+            .map(|&time| layout.section_at(time))
+            .collect();
+
+        let window_len = args.window_length.unwrap_or(DEFAULT_WINDOW_LEN);
+        let window_sections = (window_len / clock_rate / strains.section_len).round() as usize;
+
+        Self {
+            strains,
+            layout,
+            object_sections,
+            window_sections: window_sections.max(1),
+            count: args.count.unwrap_or(DEFAULT_COUNT) as usize,
+        }
+    }
+
+    fn analyze_skill(&self, peaks: &[f64]) -> js_sys::Object {
+        let obj = js_sys::Object::new();
+        let obj_as_ext = obj.unchecked_ref::<util::ObjectExt>();
+
+        obj_as_ext.set(util::static_str_to_js("spikiness"), spikiness(peaks).into());
+
+        let sections: js_sys::Array = self
+            .hardest_windows(peaks)
+            .into_iter()
+            .map(|start| self.section_to_js(peaks, start))
+            .collect();
+
+        obj_as_ext.set(util::static_str_to_js("sections"), sections.into());
+
+        obj
+    }
+
+    /// The first strain section of the hardest non-overlapping windows.
+    fn hardest_windows(&self, peaks: &[f64]) -> Vec<usize> {
+        let window_len = self.window_sections.min(peaks.len());
+
+        if window_len == 0 {
+            return Vec::new();
+        }
+
+        let mut candidates: Vec<(usize, f64)> = peaks
+            .windows(window_len)
+            .enumerate()
+            .map(|(start, window)| (start, window.iter().sum()))
+            .collect();
+
+        candidates.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+
+        let mut hardest: Vec<usize> = Vec::with_capacity(self.count);
+
+        for (start, _) in candidates {
+            if hardest.len() >= self.count {
+                break;
+            }
+
+            let overlaps = hardest
+                .iter()
+                .any(|&other| start < other + window_len && other < start + window_len);
+
+            if !overlaps {
+                hardest.push(start);
+            }
+        }
+
+        hardest
+    }
+
+    fn section_to_js(&self, peaks: &[f64], start: usize) -> JsValue {
+        let end = (start + self.window_sections).min(peaks.len());
+        let window = &peaks[start..end];
+
+        let peak = window.iter().copied().fold(0.0, f64::max);
+        let average = window.iter().sum::<f64>() / window.len() as f64;
+
+        let first_object = self.object_sections.partition_point(|&i| i < start);
+        let last_object = self.object_sections.partition_point(|&i| i < end);
+        let objects: Vec<u32> = (first_object as u32..last_object as u32).collect();
+
+        let obj = js_sys::Object::new();
+        let obj_as_ext = obj.unchecked_ref::<util::ObjectExt>();
+
+        obj_as_ext.set(
+            util::static_str_to_js("startTime"),
+            self.layout.section_start(start).into(),
+        );
+        obj_as_ext.set(
+            util::static_str_to_js("endTime"),
+            self.layout.section_end(end - 1).into(),
+        );
+        obj_as_ext.set(util::static_str_to_js("peak"), peak.into());
+        obj_as_ext.set(util::static_str_to_js("average"), average.into());
+        obj_as_ext.set(
+            util::static_str_to_js("objects"),
+            js_sys::Uint32Array::from(objects.as_slice()).into(),
+        );
+
+        obj.into()
+    }
+}
+
+impl From<StrainAnalysis<'_>> for JsStrainAnalysis {
+    fn from(analysis: StrainAnalysis<'_>) -> Self {
+        let obj = js_sys::Object::new();
+        let obj_as_ext = obj.unchecked_ref::<util::ObjectExt>();
+
+        obj_as_ext.set(
+            util::static_str_to_js("mode"),
+            (analysis.strains.mode as u8).into(),
+        );
+
+        for (key, peaks) in analysis.strains.skills() {
+            if let Some(peaks) = peaks {
+                obj_as_ext.set(
+                    util::static_str_to_js(key),
+                    analysis.analyze_skill(peaks).into(),
+                );
+            }
+        }
+
+        JsValue::from(obj).unchecked_into()
+    }
+}
+
+/// The highest strain peak divided by the average strain peak.
+fn spikiness(peaks: &[f64]) -> f64 {
+    let max = peaks.iter().copied().fold(0.0, f64::max);
+
+    if max <= 0.0 {
+        return 0.0;
+    }
+
+    let average = peaks.iter().sum::<f64>() / peaks.len() as f64;
+
+    max / average
+}
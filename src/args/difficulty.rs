@@ -1,13 +1,31 @@
+use std::collections::HashMap;
+
 use rosu_mods::GameMods;
-use rosu_pp::Difficulty;
+use rosu_pp::{Difficulty, any::DifficultyAttributes};
 use wasm_bindgen::prelude::wasm_bindgen;
 
-use crate::util;
+use crate::{
+    beatmap::JsBeatmap,
+    cache::{DifficultyCache, JsDifficultyCache, SettingsKey},
+    parallel, util,
+};
 
 #[wasm_bindgen]
 extern "C" {
     #[wasm_bindgen(typescript_type = DifficultyArgs)]
     pub type JsDifficultyArgs;
+
+    #[wasm_bindgen(typescript_type = "DifficultyArgs[]")]
+    pub type JsDifficultyArgsList;
+
+    #[wasm_bindgen(typescript_type = StrainsArgs)]
+    pub type JsStrainsArgs;
+
+    #[wasm_bindgen(typescript_type = StrainsResampleArgs)]
+    pub type JsStrainsResampleArgs;
+
+    #[wasm_bindgen(typescript_type = StrainAnalysisArgs)]
+    pub type JsStrainAnalysisArgs;
 }
 
 #[wasm_bindgen(typescript_custom_section)]
@@ -36,9 +54,101 @@ export interface DifficultyArgs extends CommonArgs {
     * Defaults to `true`.
     */
     lazer?: boolean;
+    /**
+    * Store calculated difficulty attributes in the given cache and reuse
+    * them when calculating on the same beatmap with the same settings.
+    */
+    cache?: DifficultyCache;
 }"#;
 
-#[derive(Clone, Default, serde::Deserialize)]
+#[wasm_bindgen(typescript_custom_section)]
+const _: &'static str = r#"/**
+* Options for `Difficulty.strains`.
+*/
+export interface StrainsArgs {
+    /**
+    * Whether to include a strain value and start time for each hitobject,
+    * available through `Strains.objects`.
+    *
+    * Defaults to `false`.
+    */
+    perObject?: boolean;
+    /**
+    * Whether to include the start time of each strain section, available
+    * through `Strains.sectionTimes`.
+    *
+    * Defaults to `false`.
+    */
+    sectionTimes?: boolean;
+}"#;
+
+#[derive(Default, serde::Deserialize)]
+#[serde(rename_all = "camelCase", rename = "Object")]
+pub struct StrainsArgs {
+    #[serde(default)]
+    pub per_object: bool,
+    #[serde(default)]
+    pub section_times: bool,
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const _: &'static str = r#"/**
+* Options for `Strains.resample`.
+*/
+export interface StrainsResampleArgs {
+    /**
+    * The amount of neighboring buckets on each side to average over.
+    *
+    * Defaults to `0`, i.e. no smoothing.
+    */
+    smoothing?: number;
+    /**
+    * Whether to scale each series so that its maximum is `1`.
+    *
+    * Defaults to `false`.
+    */
+    normalize?: boolean;
+}"#;
+
+#[derive(Default, serde::Deserialize)]
+#[serde(rename_all = "camelCase", rename = "Object")]
+pub struct StrainsResampleArgs {
+    #[serde(default)]
+    pub smoothing: u32,
+    #[serde(default)]
+    pub normalize: bool,
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const _: &'static str = r#"/**
+* Options for `Difficulty.analyzeStrains`.
+*/
+export interface StrainAnalysisArgs {
+    /**
+    * The maximum amount of hardest sections per skill.
+    *
+    * Defaults to `3`.
+    */
+    count?: number;
+    /**
+    * The length of a section in ms.
+    *
+    * Same as the returned times, the length is not adjusted by the clock
+    * rate. It is rounded to a multiple of the strain section length.
+    *
+    * Defaults to `4000`.
+    */
+    windowLength?: number;
+}"#;
+
+#[derive(Default, serde::Deserialize)]
+#[serde(rename_all = "camelCase", rename = "Object")]
+pub struct StrainAnalysisArgs {
+    pub count: Option<u32>,
+    pub window_length: Option<f64>,
+}
+
+#[derive(Clone, Default, PartialEq, serde::Deserialize)]
 #[serde(rename_all = "camelCase", rename = "Object")]
 pub struct DifficultyArgs {
     #[serde(default, deserialize_with = "util::deserialize_mods")]
@@ -59,9 +169,162 @@ pub struct DifficultyArgs {
     pub passed_objects: Option<u32>,
     pub hardrock_offsets: Option<bool>,
     pub lazer: Option<bool>,
+    #[serde(default, deserialize_with = "JsDifficultyCache::deserialize")]
+    pub cache: Option<DifficultyCache>,
 }
 
 impl DifficultyArgs {
+    /// Calculate the difficulty attributes, consulting the cache if there is
+    /// one.
+    pub fn calculate(&self, map: &JsBeatmap) -> DifficultyAttributes {
+        match self.cache {
+            Some(ref cache) => cache.get_or_calculate(self, map),
+            None => self.to_difficulty().calculate(&map.inner),
+        }
+    }
+
+    /// The clock rate, either specified or through the mods.
+    pub fn clock_rate(&self) -> f64 {
+        self.clock_rate
+            .or_else(|| self.mods.clock_rate())
+            .unwrap_or(1.0)
+    }
+
+    /// Calculate the difficulty attributes for each set of arguments.
+    ///
+    /// Arguments with the same settings are only calculated once and, if the
+    /// `threads` feature is enabled, distinct ones are calculated in
+    /// parallel. Caches are consulted beforehand and updated afterwards.
+    ///
+    /// Besides this deduplication, calculations do not share any work since
+    /// `rosu-pp` prepares hitobjects within each calculation.
+    pub fn calculate_many(args_list: &[Self], map: &JsBeatmap) -> Vec<DifficultyAttributes> {
+        Self::calculate_batch(args_list, &[map])
+    }
+
+    /// Calculate the difficulty attributes for each set of arguments on each
+    /// beatmap.
+    ///
+    /// The attributes of all arguments for the first beatmap come first,
+    /// followed by those of the second beatmap, and so on.
+    ///
+    /// Same as for [`DifficultyArgs::calculate_many`], identical beatmaps and
+    /// settings are only calculated once and distinct ones are calculated in
+    /// parallel if the `threads` feature is enabled.
+    pub fn calculate_batch(args_list: &[Self], maps: &[&JsBeatmap]) -> Vec<DifficultyAttributes> {
+        let mut distinct_args: Vec<DistinctArgs<'_>> = Vec::new();
+        let mut args_lookup = HashMap::new();
+
+        let args_indices: Vec<usize> = args_list
+            .iter()
+            .map(|args| {
+                let idx = *args_lookup
+                    .entry(SettingsKey::new(args))
+                    .or_insert_with(|| {
+                        distinct_args.push(DistinctArgs {
+                            args,
+                            caches: Vec::new(),
+                        });
+
+                        distinct_args.len() - 1
+                    });
+
+                let caches = &mut distinct_args[idx].caches;
+
+                let new_cache = args.cache.as_ref().filter(|cache| !caches.contains(cache));
+
+                if let Some(cache) = new_cache {
+                    caches.push(cache);
+                }
+
+                idx
+            })
+            .collect();
+
+        let mut distinct_maps: Vec<&JsBeatmap> = Vec::new();
+        let mut maps_lookup = HashMap::new();
+
+        let map_indices: Vec<usize> = maps
+            .iter()
+            .map(|map| {
+                *maps_lookup.entry(map.hash).or_insert_with(|| {
+                    distinct_maps.push(map);
+
+                    distinct_maps.len() - 1
+                })
+            })
+            .collect();
+
+        // Index of the pair of distinct beatmap and arguments
+        let pair_idx = |map_idx: usize, args_idx: usize| map_idx * distinct_args.len() + args_idx;
+
+        // Caches that do not contain the attributes of each pair yet
+        let mut missing_caches = Vec::with_capacity(distinct_maps.len() * distinct_args.len());
+
+        let mut calculated: Vec<_> = distinct_maps
+            .iter()
+            .flat_map(|map| distinct_args.iter().map(move |distinct| (map, distinct)))
+            .map(|(map, distinct)| {
+                let mut attrs = None;
+                let mut missing = Vec::new();
+
+                for cache in distinct.caches.iter() {
+                    match cache.get(distinct.args, map) {
+                        Some(cached) => attrs = Some(cached),
+                        None => missing.push(*cache),
+                    }
+                }
+
+                missing_caches.push(missing);
+
+                attrs
+            })
+            .collect();
+
+        let uncached: Vec<_> = calculated
+            .iter()
+            .enumerate()
+            .filter(|(_, attrs)| attrs.is_none())
+            .map(|(idx, _)| {
+                let map = &distinct_maps[idx / distinct_args.len()].inner;
+                let difficulty = distinct_args[idx % distinct_args.len()]
+                    .args
+                    .to_difficulty();
+
+                (idx, map, difficulty)
+            })
+            .collect();
+
+        let uncached = parallel::map(uncached, |(idx, map, difficulty)| {
+            (idx, difficulty.calculate(map))
+        });
+
+        for (idx, attrs) in uncached {
+            calculated[idx] = Some(attrs);
+        }
+
+        for (idx, missing) in missing_caches.into_iter().enumerate() {
+            let map = distinct_maps[idx / distinct_args.len()];
+            let args = distinct_args[idx % distinct_args.len()].args;
+
+            if let Some(ref attrs) = calculated[idx] {
+                for cache in missing {
+                    cache.insert(args, map, attrs.clone());
+                }
+            }
+        }
+
+        map_indices
+            .into_iter()
+            .flat_map(|map_idx| {
+                args_indices
+                    .iter()
+                    .map(move |&args_idx| pair_idx(map_idx, args_idx))
+            })
+            .filter_map(|idx| calculated[idx].clone())
+            .collect()
+    }
+
     pub fn to_difficulty(&self) -> Difficulty {
         let mut difficulty = Difficulty::new().mods(self.mods.clone());
 
@@ -100,3 +363,10 @@ impl DifficultyArgs {
         difficulty
     }
 }
+
+/// Arguments with distinct settings alongside the caches of all arguments
+/// with the same settings.
+struct DistinctArgs<'a> {
+    args: &'a DifficultyArgs,
+    caches: Vec<&'a DifficultyCache>,
+}
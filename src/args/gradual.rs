@@ -0,0 +1,111 @@
+use serde::de;
+use wasm_bindgen::prelude::wasm_bindgen;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = HitEvent)]
+    pub type JsHitEvent;
+
+    #[wasm_bindgen(typescript_type = "HitEvent[]")]
+    pub type JsHitEventList;
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const _: &'static str = r#"/**
+* The judgement of a single hit object for `GradualPerformance.nextHit`.
+*/
+export interface HitEvent {
+    /**
+    * The hitresult of the hit object itself.
+    *
+    * For osu!catch, fruits are either `Great` or `Miss` and droplets are
+    * either `Ok` or `Miss`. Tiny droplets are not considered hit objects;
+    * specify them through `smallTickHits` and `smallTickMisses` of the
+    * following fruit or droplet instead.
+    */
+    result: HitResult;
+    /**
+    * The amount of hit "large ticks" that belong to this hit object.
+    *
+    * Only relevant for osu!standard sliders, see
+    * `PerformanceArgs.largeTickHits`.
+    */
+    largeTickHits?: number;
+    /**
+    * The amount of hit "small ticks" that belong to this hit object.
+    *
+    * Relevant for osu!standard sliders, see
+    * `PerformanceArgs.smallTickHits`, and osu!catch for which it represents
+    * the amount of hit tiny droplets.
+    */
+    smallTickHits?: number;
+    /**
+    * The amount of missed tiny droplets.
+    *
+    * Only relevant for osu!catch.
+    */
+    smallTickMisses?: number;
+    /**
+    * Whether the slider end was hit.
+    *
+    * Only relevant for osu!standard sliders in lazer.
+    */
+    sliderEndHit?: boolean;
+    /**
+    * The current combo after this hit object.
+    *
+    * If not specified, the combo is tracked by incrementing it for each
+    * hit object as well as hit large ticks and slider ends, and resetting it
+    * on misses.
+    */
+    combo?: number;
+}"#;
+
+#[derive(Clone, serde::Deserialize)]
+#[serde(rename_all = "camelCase", rename = "Object")]
+pub struct HitEvent {
+    #[serde(deserialize_with = "JsHitResult::deserialize")]
+    pub result: JsHitResult,
+    #[serde(default)]
+    pub large_tick_hits: u32,
+    #[serde(default)]
+    pub small_tick_hits: u32,
+    #[serde(default)]
+    pub small_tick_misses: u32,
+    #[serde(default)]
+    pub slider_end_hit: bool,
+    pub combo: Option<u32>,
+}
+
+/// The judgement of a hit object.
+#[wasm_bindgen(js_name = HitResult)]
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum JsHitResult {
+    Miss,
+    /// 50
+    Meh,
+    /// 100
+    Ok,
+    /// 200 in osu!mania, otherwise treated as `Ok`
+    Good,
+    /// 300
+    Great,
+    /// 320 in osu!mania, otherwise treated as `Great`
+    Perfect,
+}
+
+impl JsHitResult {
+    fn deserialize<'de, D: de::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        let result = match <u8 as de::Deserialize>::deserialize(d) {
+            Ok(0) => Self::Miss,
+            Ok(1) => Self::Meh,
+            Ok(2) => Self::Ok,
+            Ok(3) => Self::Good,
+            Ok(4) => Self::Great,
+            Ok(5) => Self::Perfect,
+            _ => return Err(de::Error::custom("invalid HitResult")),
+        };
+
+        Ok(result)
+    }
+}
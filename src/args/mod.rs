@@ -1,4 +1,6 @@
 pub mod beatmap;
 pub mod common;
 pub mod difficulty;
+pub mod gradual;
 pub mod performance;
+pub mod score_state;
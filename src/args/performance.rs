@@ -1,7 +1,7 @@
 use rosu_mods::GameMods;
 use rosu_pp::{
     Performance,
-    any::{DifficultyAttributes, HitResultPriority},
+    any::{DifficultyAttributes, HitResultPriority, ScoreState},
 };
 use serde::de;
 use wasm_bindgen::{__rt::RcRef, JsValue, prelude::wasm_bindgen};
@@ -10,6 +10,7 @@ use crate::{
     JsError, JsResult,
     attributes::{difficulty::JsDifficultyAttributes, performance::JsPerformanceAttributes},
     beatmap::JsBeatmap,
+    cache::{DifficultyCache, JsDifficultyCache},
     deserializer::JsDeserializer,
     util,
 };
@@ -21,8 +22,17 @@ extern "C" {
     #[wasm_bindgen(typescript_type = PerformanceArgs)]
     pub type JsPerformanceArgs;
 
+    #[wasm_bindgen(typescript_type = "PerformanceArgs[]")]
+    pub type JsPerformanceArgsList;
+
     #[wasm_bindgen(typescript_type = "MapOrAttributes")]
     pub type JsMapOrAttributes;
+
+    #[wasm_bindgen(typescript_type = PerformanceScenariosArgs)]
+    pub type JsPerformanceScenariosArgs;
+
+    #[wasm_bindgen(typescript_type = SolveForPpArgs)]
+    pub type JsSolveForPpArgs;
 }
 
 #[wasm_bindgen(typescript_custom_section)]
@@ -107,7 +117,7 @@ export interface PerformanceArgs extends DifficultyArgs {
     hitresultPriority?: HitResultPriority;
 }"#;
 
-#[derive(Default, serde::Deserialize)]
+#[derive(Clone, Default, serde::Deserialize)]
 #[serde(rename_all = "camelCase", rename = "Object")]
 pub struct PerformanceArgs {
     #[serde(default, deserialize_with = "util::deserialize_mods")]
@@ -128,6 +138,8 @@ pub struct PerformanceArgs {
     pub passed_objects: Option<u32>,
     pub hardrock_offsets: Option<bool>,
     pub lazer: Option<bool>,
+    #[serde(default, deserialize_with = "JsDifficultyCache::deserialize")]
+    pub cache: Option<DifficultyCache>,
     pub accuracy: Option<f64>,
     pub combo: Option<u32>,
     pub large_tick_hits: Option<u32>,
@@ -166,7 +178,7 @@ impl From<JsHitResultPriority> for HitResultPriority {
 }
 
 impl JsHitResultPriority {
-    fn deserialize<'de, D: de::Deserializer<'de>>(d: D) -> Result<HitResultPriority, D::Error> {
+    pub fn deserialize<'de, D: de::Deserializer<'de>>(d: D) -> Result<HitResultPriority, D::Error> {
         let priority = match <u8 as de::Deserialize>::deserialize(d) {
             Ok(0) => HitResultPriority::BestCase,
             Ok(1) => HitResultPriority::WorstCase,
@@ -224,7 +236,44 @@ impl PerformanceArgs {
             perf = perf.misses(misses);
         }
 
-        let difficulty = DifficultyArgs {
+        perf.hitresult_priority(self.hitresult_priority)
+            .difficulty(self.difficulty_args().to_difficulty())
+    }
+
+    /// Use the hitresults and combo of the [`ScoreState`] instead of
+    /// generating them.
+    pub fn set_state(&mut self, state: &ScoreState) {
+        self.accuracy = None;
+        self.combo = Some(state.max_combo);
+        self.large_tick_hits = Some(state.osu_large_tick_hits);
+        self.small_tick_hits = Some(state.osu_small_tick_hits);
+        self.slider_end_hits = Some(state.slider_end_hits);
+        self.n_geki = Some(state.n_geki);
+        self.n_katu = Some(state.n_katu);
+        self.n300 = Some(state.n300);
+        self.n100 = Some(state.n100);
+        self.n50 = Some(state.n50);
+        self.misses = Some(state.misses);
+    }
+
+    /// Remove all specified hitresults, combo, and accuracy.
+    pub fn clear_state(&mut self) {
+        self.accuracy = None;
+        self.combo = None;
+        self.large_tick_hits = None;
+        self.small_tick_hits = None;
+        self.slider_end_hits = None;
+        self.n_geki = None;
+        self.n_katu = None;
+        self.n300 = None;
+        self.n100 = None;
+        self.n50 = None;
+        self.misses = None;
+    }
+
+    /// The arguments that are relevant for difficulty calculation.
+    pub fn difficulty_args(&self) -> DifficultyArgs {
+        DifficultyArgs {
             mods: self.mods.clone(),
             clock_rate: self.clock_rate,
             ar: self.ar,
@@ -238,10 +287,73 @@ impl PerformanceArgs {
             passed_objects: self.passed_objects,
             hardrock_offsets: self.hardrock_offsets,
             lazer: self.lazer,
+            cache: self.cache.clone(),
+        }
+    }
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const _: &'static str = r#"/**
+* Arguments to provide `Performance.calculateScenarios`.
+*/
+export interface PerformanceScenariosArgs {
+    /**
+    * Accuracies between `0.0` and `100.0` for which the performance of a
+    * full combo should be calculated.
+    */
+    accuracies?: number[];
+}"#;
+
+#[derive(Default, serde::Deserialize)]
+#[serde(rename_all = "camelCase", rename = "Object")]
+pub struct PerformanceScenariosArgs {
+    #[serde(default)]
+    pub accuracies: Vec<f64>,
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const _: &'static str = r#"/**
+* The argument that `Performance.solveForPp` varies.
+*/
+export type SolveForPpVariable = "accuracy" | "misses" | "combo";
+
+/**
+* Arguments to provide `Performance.solveForPp`.
+*/
+export interface SolveForPpArgs {
+    /**
+    * The argument to search over.
+    *
+    * Defaults to `"accuracy"`.
+    */
+    vary?: SolveForPpVariable;
+}"#;
+
+#[derive(Default, serde::Deserialize)]
+#[serde(rename_all = "camelCase", rename = "Object")]
+pub struct SolveForPpArgs {
+    #[serde(default, deserialize_with = "SolveForPpVariable::deserialize")]
+    pub vary: SolveForPpVariable,
+}
+
+#[derive(Copy, Clone, Default)]
+pub enum SolveForPpVariable {
+    #[default]
+    Accuracy,
+    Misses,
+    Combo,
+}
+
+impl SolveForPpVariable {
+    fn deserialize<'de, D: de::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        let variable = match <String as de::Deserialize>::deserialize(d).as_deref() {
+            Ok("accuracy") => Self::Accuracy,
+            Ok("misses") => Self::Misses,
+            Ok("combo") => Self::Combo,
+            _ => return Err(de::Error::custom("invalid SolveForPpVariable")),
         };
 
-        perf.hitresult_priority(self.hitresult_priority)
-            .difficulty(difficulty.to_difficulty())
+        Ok(variable)
     }
 }
 
@@ -0,0 +1,155 @@
+use rosu_mods::GameMods;
+use rosu_pp::any::HitResultPriority;
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::{attributes::difficulty::JsDifficultyAttributes, score_state::SliderCounts, util};
+
+use super::performance::JsHitResultPriority;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = StateFromAccuracyArgs)]
+    pub type JsStateFromAccuracyArgs;
+
+    #[wasm_bindgen(typescript_type = StateAccuracyArgs)]
+    pub type JsStateAccuracyArgs;
+
+    #[wasm_bindgen(typescript_type = StateValidationArgs)]
+    pub type JsStateValidationArgs;
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const _: &'static str = r#"/**
+* Arguments to provide `ScoreStateUtils.fromAccuracy`.
+*/
+export interface StateFromAccuracyArgs {
+    /**
+    * The accuracy between `0.0` and `100.0`.
+    *
+    * Defaults to `100.0`.
+    */
+    accuracy?: number;
+    /**
+    * The amount of misses.
+    */
+    misses?: number;
+    /**
+    * The max combo of the play.
+    *
+    * Defaults to the maximum possible combo considering misses.
+    */
+    combo?: number;
+    /**
+    * Whether the score was set on osu!lazer or osu!stable.
+    *
+    * Defaults to `true`.
+    */
+    lazer?: boolean;
+    /**
+    * Specify mods.
+    *
+    * Only relevant for the `CL` mod which affects how slider ticks and
+    * osu!mania's hitresults are weighted in osu!lazer.
+    *
+    * See `CommonArgs.mods` for the accepted types.
+    */
+    mods?: Object;
+    /**
+    * Specify how hitresults should be generated.
+    *
+    * Defaults to `HitResultPriority.BestCase`.
+    */
+    priority?: HitResultPriority;
+}"#;
+
+#[derive(Default, serde::Deserialize)]
+#[serde(rename_all = "camelCase", rename = "Object")]
+pub struct StateFromAccuracyArgs {
+    pub accuracy: Option<f64>,
+    pub misses: Option<u32>,
+    pub combo: Option<u32>,
+    pub lazer: Option<bool>,
+    #[serde(default, deserialize_with = "util::deserialize_mods")]
+    pub mods: GameMods,
+    #[serde(default, deserialize_with = "JsHitResultPriority::deserialize")]
+    pub priority: HitResultPriority,
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const _: &'static str = r#"/**
+* Arguments to provide `ScoreStateUtils.accuracy` and `ScoreStateUtils.grade`.
+*/
+export interface StateAccuracyArgs {
+    /**
+    * Whether the score was set on osu!lazer or osu!stable.
+    *
+    * Defaults to `true`.
+    */
+    lazer?: boolean;
+    /**
+    * Specify mods.
+    *
+    * Relevant for the `CL` mod which affects how slider ticks and osu!mania's
+    * hitresults are weighted in osu!lazer, as well as for silver grades.
+    *
+    * See `CommonArgs.mods` for the accepted types.
+    */
+    mods?: Object;
+    /**
+    * Difficulty attributes of the beatmap.
+    *
+    * Only relevant for osu!lazer scores in osu! to determine the maximum
+    * amount of slider-related hitresults.
+    */
+    attributes?: DifficultyAttributes;
+}"#;
+
+#[derive(Default, serde::Deserialize)]
+#[serde(rename_all = "camelCase", rename = "Object")]
+pub struct StateAccuracyArgs {
+    pub lazer: Option<bool>,
+    #[serde(default, deserialize_with = "util::deserialize_mods")]
+    pub mods: GameMods,
+    pub attributes: Option<JsDifficultyAttributes>,
+}
+
+impl StateAccuracyArgs {
+    pub fn slider_counts(&self) -> Option<SliderCounts> {
+        let attrs = self.attributes.as_ref()?;
+
+        Some(SliderCounts {
+            n_sliders: attrs.n_sliders?,
+            n_large_ticks: attrs.n_large_ticks?,
+        })
+    }
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const _: &'static str = r#"/**
+* Arguments to provide `ScoreStateUtils.validate`.
+*/
+export interface StateValidationArgs {
+    /**
+    * Whether the score was set on osu!lazer or osu!stable.
+    *
+    * Defaults to `true`.
+    */
+    lazer?: boolean;
+    /**
+    * Specify mods.
+    *
+    * Relevant for the `CL` mod which affects the amount of osu!standard
+    * slider-related hitresults and osu!mania's hitresults in osu!lazer.
+    *
+    * See `CommonArgs.mods` for the accepted types.
+    */
+    mods?: Object;
+}"#;
+
+#[derive(Default, serde::Deserialize)]
+#[serde(rename_all = "camelCase", rename = "Object")]
+pub struct StateValidationArgs {
+    pub lazer: Option<bool>,
+    #[serde(default, deserialize_with = "util::deserialize_mods")]
+    pub mods: GameMods,
+}
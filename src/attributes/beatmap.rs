@@ -6,6 +6,7 @@ use crate::{
     args::beatmap::{BeatmapAttributesArgs, JsBeatmapAttributesArgs},
     beatmap::JsBeatmap,
     deserializer::JsDeserializer,
+    json::{self, JsJson, JsonObject},
     mode::JsGameMode,
     mods::JsGameMods,
     util,
@@ -120,7 +121,9 @@ impl JsBeatmapAttributesBuilder {
     }
 }
 
-#[wasm_bindgen(js_name = BeatmapAttributes, inspectable)]
+#[wasm_bindgen(js_name = BeatmapAttributes)]
+#[derive(Default, serde::Deserialize)]
+#[serde(rename = "BeatmapAttributes", rename_all = "camelCase")]
 pub struct JsBeatmapAttributes {
     /// The approach rate.
     #[wasm_bindgen(readonly)]
@@ -139,25 +142,67 @@ pub struct JsBeatmapAttributes {
     pub clock_rate: f64,
     /// Hit window for approach rate i.e. TimePreempt in milliseconds.
     #[wasm_bindgen(js_name = "arHitWindow", readonly)]
+    #[serde(rename = "arHitWindow")]
     pub ar_hitwindow: f64,
     /// Hit window for overall difficulty i.e. time to hit a 300 ("Great") in
     /// milliseconds.
     #[wasm_bindgen(js_name = "odGreatHitWindow", readonly)]
+    #[serde(rename = "odGreatHitWindow")]
     pub od_great_hitwindow: f64,
     /// Hit window for overall difficulty i.e. time to hit a 100 ("Ok") in
     /// milliseconds.
     ///
     /// Not available for osu!mania.
     #[wasm_bindgen(js_name = "odOkHitWindow", readonly)]
+    #[serde(rename = "odOkHitWindow")]
     pub od_ok_hitwindow: Option<f64>,
     /// Hit window for overall difficulty i.e. time to hit a 50 ("Meh") in
     /// milliseconds.
     ///
     /// Only available for osu!.
     #[wasm_bindgen(js_name = "odMehHitWindow", readonly)]
+    #[serde(rename = "odMehHitWindow")]
     pub od_meh_hitwindow: Option<f64>,
 }
 
+#[wasm_bindgen(js_class = BeatmapAttributes)]
+impl JsBeatmapAttributes {
+    /// Convert the attributes into a plain object that includes a schema
+    /// version.
+    ///
+    /// Unavailable attributes are omitted.
+    #[wasm_bindgen(js_name = toJSON)]
+    pub fn to_json(&self) -> JsJson {
+        let obj = JsonObject::versioned();
+        obj.set("ar", self.ar);
+        obj.set("od", self.od);
+        obj.set("cs", self.cs);
+        obj.set("hp", self.hp);
+        obj.set("clockRate", self.clock_rate);
+        obj.set("arHitWindow", self.ar_hitwindow);
+        obj.set("odGreatHitWindow", self.od_great_hitwindow);
+        obj.set_opt("odOkHitWindow", self.od_ok_hitwindow);
+        obj.set_opt("odMehHitWindow", self.od_meh_hitwindow);
+
+        obj.into()
+    }
+
+    /// Stringified version of `BeatmapAttributes.toJSON`.
+    #[wasm_bindgen(js_name = toString)]
+    pub fn to_json_string(&self) -> String {
+        json::stringify(&self.to_json())
+    }
+
+    /// Create attributes from the result of `BeatmapAttributes.toJSON`.
+    /// @throws Throws an error if the JSON is invalid or of an unsupported schema version
+    #[wasm_bindgen(js_name = fromJSON)]
+    pub fn from_json(json: &JsJson) -> JsResult<JsBeatmapAttributes> {
+        json::check_version(json)?;
+
+        util::from_value::<JsBeatmapAttributes>(json)
+    }
+}
+
 impl From<BeatmapAttributes> for JsBeatmapAttributes {
     fn from(attrs: BeatmapAttributes) -> Self {
         let BeatmapAttributes {
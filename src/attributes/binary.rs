@@ -0,0 +1,382 @@
+use std::{error, fmt};
+
+use rosu_pp::{
+    any::DifficultyAttributes, catch::CatchDifficultyAttributes, mania::ManiaDifficultyAttributes,
+    osu::OsuDifficultyAttributes, taiko::TaikoDifficultyAttributes,
+};
+
+/// The current version of the binary format.
+///
+/// Has to be incremented whenever the layout of any mode changes.
+const VERSION: u8 = 1;
+
+const OSU: u8 = 0;
+const TAIKO: u8 = 1;
+const CATCH: u8 = 2;
+const MANIA: u8 = 3;
+
+/// Encode [`DifficultyAttributes`] into bytes.
+///
+/// The first byte is the format version and the second byte is the mode.
+/// The mode's attributes follow in little endian with a fixed layout.
+pub fn encode(attrs: &DifficultyAttributes) -> Vec<u8> {
+    let mut writer = Writer::default();
+    writer.u8(VERSION);
+
+    match attrs {
+        DifficultyAttributes::Osu(attrs) => {
+            writer.u8(OSU);
+            writer.f64(attrs.stars);
+            writer.u32(attrs.max_combo);
+            writer.f64(attrs.aim);
+            writer.f64(attrs.aim_difficult_slider_count);
+            writer.f64(attrs.speed);
+            writer.f64(attrs.flashlight);
+            writer.f64(attrs.slider_factor);
+            writer.f64(attrs.speed_note_count);
+            writer.f64(attrs.aim_difficult_strain_count);
+            writer.f64(attrs.speed_difficult_strain_count);
+            writer.f64(attrs.ar);
+            writer.f64(attrs.great_hit_window);
+            writer.f64(attrs.ok_hit_window);
+            writer.f64(attrs.meh_hit_window);
+            writer.f64(attrs.hp);
+            writer.u32(attrs.n_circles);
+            writer.u32(attrs.n_sliders);
+            writer.u32(attrs.n_large_ticks);
+            writer.u32(attrs.n_spinners);
+        }
+        DifficultyAttributes::Taiko(attrs) => {
+            writer.u8(TAIKO);
+            writer.f64(attrs.stars);
+            writer.u32(attrs.max_combo);
+            writer.bool(attrs.is_convert);
+            writer.f64(attrs.stamina);
+            writer.f64(attrs.reading);
+            writer.f64(attrs.rhythm);
+            writer.f64(attrs.color);
+            writer.f64(attrs.great_hit_window);
+            writer.f64(attrs.ok_hit_window);
+            writer.f64(attrs.mono_stamina_factor);
+        }
+        DifficultyAttributes::Catch(attrs) => {
+            writer.u8(CATCH);
+            writer.f64(attrs.stars);
+            writer.bool(attrs.is_convert);
+            writer.f64(attrs.ar);
+            writer.u32(attrs.n_fruits);
+            writer.u32(attrs.n_droplets);
+            writer.u32(attrs.n_tiny_droplets);
+        }
+        DifficultyAttributes::Mania(attrs) => {
+            writer.u8(MANIA);
+            writer.f64(attrs.stars);
+            writer.u32(attrs.max_combo);
+            writer.bool(attrs.is_convert);
+            writer.u32(attrs.n_objects);
+            writer.u32(attrs.n_hold_notes);
+        }
+    }
+
+    writer.bytes
+}
+
+/// Decode [`DifficultyAttributes`] from bytes that were created through
+/// [`encode`].
+pub fn decode(bytes: &[u8]) -> Result<DifficultyAttributes, DecodeAttributesError> {
+    let mut reader = Reader::new(bytes);
+
+    let version = reader.u8()?;
+
+    if version != VERSION {
+        return Err(DecodeAttributesError::Version(version));
+    }
+
+    let attrs = match reader.u8()? {
+        OSU => DifficultyAttributes::Osu(OsuDifficultyAttributes {
+            stars: reader.f64()?,
+            max_combo: reader.u32()?,
+            aim: reader.f64()?,
+            aim_difficult_slider_count: reader.f64()?,
+            speed: reader.f64()?,
+            flashlight: reader.f64()?,
+            slider_factor: reader.f64()?,
+            speed_note_count: reader.f64()?,
+            aim_difficult_strain_count: reader.f64()?,
+            speed_difficult_strain_count: reader.f64()?,
+            ar: reader.f64()?,
+            great_hit_window: reader.f64()?,
+            ok_hit_window: reader.f64()?,
+            meh_hit_window: reader.f64()?,
+            hp: reader.f64()?,
+            n_circles: reader.u32()?,
+            n_sliders: reader.u32()?,
+            n_large_ticks: reader.u32()?,
+            n_spinners: reader.u32()?,
+        }),
+        TAIKO => DifficultyAttributes::Taiko(TaikoDifficultyAttributes {
+            stars: reader.f64()?,
+            max_combo: reader.u32()?,
+            is_convert: reader.bool()?,
+            stamina: reader.f64()?,
+            reading: reader.f64()?,
+            rhythm: reader.f64()?,
+            color: reader.f64()?,
+            great_hit_window: reader.f64()?,
+            ok_hit_window: reader.f64()?,
+            mono_stamina_factor: reader.f64()?,
+        }),
+        CATCH => DifficultyAttributes::Catch(CatchDifficultyAttributes {
+            stars: reader.f64()?,
+            is_convert: reader.bool()?,
+            ar: reader.f64()?,
+            n_fruits: reader.u32()?,
+            n_droplets: reader.u32()?,
+            n_tiny_droplets: reader.u32()?,
+        }),
+        MANIA => DifficultyAttributes::Mania(ManiaDifficultyAttributes {
+            stars: reader.f64()?,
+            max_combo: reader.u32()?,
+            is_convert: reader.bool()?,
+            n_objects: reader.u32()?,
+            n_hold_notes: reader.u32()?,
+        }),
+        mode => return Err(DecodeAttributesError::Mode(mode)),
+    };
+
+    if !reader.is_empty() {
+        return Err(DecodeAttributesError::TrailingBytes);
+    }
+
+    Ok(attrs)
+}
+
+#[derive(Default)]
+struct Writer {
+    bytes: Vec<u8>,
+}
+
+impl Writer {
+    fn u8(&mut self, value: u8) {
+        self.bytes.push(value);
+    }
+
+    fn bool(&mut self, value: bool) {
+        self.u8(u8::from(value));
+    }
+
+    fn u32(&mut self, value: u32) {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn f64(&mut self, value: f64) {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    const fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+
+    const fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], DecodeAttributesError> {
+        if self.bytes.len() < N {
+            return Err(DecodeAttributesError::UnexpectedEof);
+        }
+
+        let (bytes, rest) = self.bytes.split_at(N);
+        self.bytes = rest;
+
+        Ok(bytes.try_into().unwrap())
+    }
+
+    fn u8(&mut self) -> Result<u8, DecodeAttributesError> {
+        self.array().map(|[byte]| byte)
+    }
+
+    fn bool(&mut self) -> Result<bool, DecodeAttributesError> {
+        match self.u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(DecodeAttributesError::Bool),
+        }
+    }
+
+    fn u32(&mut self) -> Result<u32, DecodeAttributesError> {
+        self.array().map(u32::from_le_bytes)
+    }
+
+    fn f64(&mut self) -> Result<f64, DecodeAttributesError> {
+        self.array().map(f64::from_le_bytes)
+    }
+}
+
+/// All the ways that decoding [`DifficultyAttributes`] can fail.
+#[derive(Debug)]
+pub enum DecodeAttributesError {
+    Bool,
+    Mode(u8),
+    TrailingBytes,
+    UnexpectedEof,
+    Version(u8),
+}
+
+impl error::Error for DecodeAttributesError {}
+
+impl fmt::Display for DecodeAttributesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Bool => f.write_str("invalid boolean"),
+            Self::Mode(mode) => write!(f, "invalid mode {mode}"),
+            Self::TrailingBytes => f.write_str("unexpected trailing bytes"),
+            Self::UnexpectedEof => f.write_str("unexpected end of input"),
+            Self::Version(version) => write!(f, "unsupported version {version}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attributes() -> [DifficultyAttributes; 4] {
+        [
+            DifficultyAttributes::Osu(OsuDifficultyAttributes {
+                stars: 6.51,
+                max_combo: 1234,
+                aim: 3.2,
+                aim_difficult_slider_count: 42.5,
+                speed: 2.9,
+                flashlight: 0.5,
+                slider_factor: 0.98,
+                speed_note_count: 321.7,
+                aim_difficult_strain_count: 150.25,
+                speed_difficult_strain_count: 120.75,
+                ar: 9.7,
+                great_hit_window: 25.5,
+                ok_hit_window: 60.5,
+                meh_hit_window: 95.5,
+                hp: 5.0,
+                n_circles: 500,
+                n_sliders: 300,
+                n_large_ticks: 120,
+                n_spinners: 2,
+            }),
+            DifficultyAttributes::Taiko(TaikoDifficultyAttributes {
+                stars: 4.75,
+                max_combo: 987,
+                is_convert: true,
+                stamina: 2.1,
+                reading: 0.3,
+                rhythm: 1.2,
+                color: 1.8,
+                great_hit_window: 35.0,
+                ok_hit_window: 80.0,
+                mono_stamina_factor: 0.12,
+            }),
+            DifficultyAttributes::Catch(CatchDifficultyAttributes {
+                stars: 5.2,
+                is_convert: false,
+                ar: 9.0,
+                n_fruits: 800,
+                n_droplets: 150,
+                n_tiny_droplets: 1200,
+            }),
+            DifficultyAttributes::Mania(ManiaDifficultyAttributes {
+                stars: 3.9,
+                max_combo: 2500,
+                is_convert: true,
+                n_objects: 1800,
+                n_hold_notes: 400,
+            }),
+        ]
+    }
+
+    #[test]
+    fn round_trip() {
+        for attrs in attributes() {
+            assert_eq!(decode(&encode(&attrs)).unwrap(), attrs);
+        }
+    }
+
+    #[test]
+    fn truncated() {
+        for attrs in attributes() {
+            let bytes = encode(&attrs);
+
+            for len in 0..bytes.len() {
+                assert!(matches!(
+                    decode(&bytes[..len]),
+                    Err(DecodeAttributesError::UnexpectedEof)
+                ));
+            }
+        }
+    }
+
+    #[test]
+    fn invalid_header() {
+        let mut bytes = encode(&attributes()[0]);
+
+        bytes[1] = 4;
+        assert!(matches!(
+            decode(&bytes),
+            Err(DecodeAttributesError::Mode(4))
+        ));
+
+        bytes[0] = VERSION + 1;
+        assert!(matches!(
+            decode(&bytes),
+            Err(DecodeAttributesError::Version(_))
+        ));
+    }
+
+    #[test]
+    fn invalid_bool() {
+        let mut bytes = encode(&attributes()[3]);
+
+        // Version, mode, stars, and max combo precede `is_convert`
+        bytes[1 + 1 + 8 + 4] = 2;
+        assert!(matches!(decode(&bytes), Err(DecodeAttributesError::Bool)));
+    }
+
+    #[test]
+    fn trailing_bytes() {
+        for attrs in attributes() {
+            let mut bytes = encode(&attrs);
+            bytes.push(0);
+
+            assert!(matches!(
+                decode(&bytes),
+                Err(DecodeAttributesError::TrailingBytes)
+            ));
+        }
+    }
+
+    #[test]
+    fn garbage() {
+        let mut state = 0x9E37_79B9_u32;
+
+        for len in 0..256 {
+            let bytes: Vec<u8> = (0..len)
+                .map(|_| {
+                    state ^= state << 13;
+                    state ^= state >> 17;
+                    state ^= state << 5;
+
+                    state as u8
+                })
+                .collect();
+
+            // Must not panic
+            let _ = decode(&bytes);
+        }
+    }
+}
@@ -4,10 +4,17 @@ use rosu_pp::{
 };
 use wasm_bindgen::prelude::wasm_bindgen;
 
-use crate::{JsError, mode::JsGameMode};
+use crate::{
+    JsError, JsResult,
+    json::{self, JsJson, JsonObject},
+    mode::JsGameMode,
+    util,
+};
+
+use super::binary;
 
 /// The result of a difficulty calculation.
-#[wasm_bindgen(js_name = DifficultyAttributes, inspectable)]
+#[wasm_bindgen(js_name = DifficultyAttributes)]
 #[derive(Clone, Default, serde::Deserialize)]
 #[serde(rename = "DifficultyAttributes", rename_all = "camelCase")]
 pub struct JsDifficultyAttributes {
@@ -171,6 +178,100 @@ pub struct JsDifficultyAttributes {
     pub max_combo: u32,
 }
 
+#[wasm_bindgen(js_class = DifficultyAttributes)]
+impl JsDifficultyAttributes {
+    /// Encode the attributes into a compact binary format.
+    ///
+    /// The bytes start with a format version and the mode, followed by the
+    /// mode's attributes. Decoding them via `DifficultyAttributes.fromBytes`
+    /// yields the exact same attributes.
+    /// @throws Throws an error if the attributes are invalid
+    #[wasm_bindgen(js_name = toBytes)]
+    pub fn to_bytes(&self) -> JsResult<Vec<u8>> {
+        let attrs = DifficultyAttributes::try_from(self.clone())?;
+
+        Ok(binary::encode(&attrs))
+    }
+
+    /// Decode attributes from bytes that were created through
+    /// `DifficultyAttributes.toBytes`.
+    /// @throws Throws an error if the bytes are invalid or of an unsupported version
+    #[wasm_bindgen(js_name = fromBytes)]
+    pub fn from_bytes(bytes: &[u8]) -> JsResult<JsDifficultyAttributes> {
+        binary::decode(bytes)
+            .map(Self::from)
+            .map_err(|err| JsError::new(&format!("Failed to decode attributes: {err}")))
+    }
+
+    /// Convert the attributes into a plain object that includes a schema
+    /// version.
+    ///
+    /// Unavailable attributes are omitted.
+    #[wasm_bindgen(js_name = toJSON)]
+    pub fn to_json(&self) -> JsJson {
+        let obj = JsonObject::versioned();
+        self.json_fields(&obj);
+
+        obj.into()
+    }
+
+    /// Stringified version of `DifficultyAttributes.toJSON`.
+    #[wasm_bindgen(js_name = toString)]
+    pub fn to_json_string(&self) -> String {
+        json::stringify(&self.to_json())
+    }
+
+    /// Create attributes from the result of `DifficultyAttributes.toJSON`.
+    /// @throws Throws an error if the JSON is invalid or of an unsupported schema version
+    #[wasm_bindgen(js_name = fromJSON)]
+    pub fn from_json(json: &JsJson) -> JsResult<JsDifficultyAttributes> {
+        json::check_version(json)?;
+        let attrs = util::from_value::<JsDifficultyAttributes>(json)?;
+
+        DifficultyAttributes::try_from(attrs).map(Self::from)
+    }
+}
+
+impl JsDifficultyAttributes {
+    /// Set all available attributes on the given object.
+    pub fn json_fields(&self, obj: &JsonObject) {
+        obj.set("mode", self.mode as u8);
+        obj.set("stars", self.stars);
+        obj.set("isConvert", self.is_convert);
+        obj.set_opt("aim", self.aim);
+        obj.set_opt("aimDifficultSliderCount", self.aim_difficult_slider_count);
+        obj.set_opt("speed", self.speed);
+        obj.set_opt("flashlight", self.flashlight);
+        obj.set_opt("sliderFactor", self.slider_factor);
+        obj.set_opt("speedNoteCount", self.speed_note_count);
+        obj.set_opt("aimDifficultStrainCount", self.aim_difficult_strain_count);
+        obj.set_opt(
+            "speedDifficultStrainCount",
+            self.speed_difficult_strain_count,
+        );
+        obj.set_opt("hp", self.hp);
+        obj.set_opt("nCircles", self.n_circles);
+        obj.set_opt("nSliders", self.n_sliders);
+        obj.set_opt("nLargeTicks", self.n_large_ticks);
+        obj.set_opt("nSpinners", self.n_spinners);
+        obj.set_opt("stamina", self.stamina);
+        obj.set_opt("rhythm", self.rhythm);
+        obj.set_opt("color", self.color);
+        obj.set_opt("reading", self.reading);
+        obj.set_opt("nFruits", self.n_fruits);
+        obj.set_opt("nDroplets", self.n_droplets);
+        obj.set_opt("nTinyDroplets", self.n_tiny_droplets);
+        obj.set_opt("nObjects", self.n_objects);
+        obj.set_opt("nHoldNotes", self.n_hold_notes);
+        obj.set_opt("ar", self.ar);
+        obj.set_opt("greatHitWindow", self.great_hit_window);
+        obj.set_opt("okHitWindow", self.ok_hit_window);
+        obj.set_opt("mehHitWindow", self.meh_hit_window);
+        obj.set_opt("monoStaminaFactor", self.mono_stamina_factor);
+        obj.set("maxCombo", self.max_combo);
+    }
+}
+
 impl From<OsuDifficultyAttributes> for JsDifficultyAttributes {
     fn from(attrs: OsuDifficultyAttributes) -> Self {
         let OsuDifficultyAttributes {
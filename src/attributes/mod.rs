@@ -1,3 +1,5 @@
 pub mod beatmap;
 pub mod difficulty;
 pub mod performance;
+
+mod binary;
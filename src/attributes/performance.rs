@@ -1,7 +1,7 @@
 use std::fmt::{Formatter, Result as FmtResult};
 
 use rosu_pp::{
-    any::{PerformanceAttributes, ScoreState},
+    any::{DifficultyAttributes, PerformanceAttributes, ScoreState},
     catch::CatchPerformanceAttributes,
     mania::ManiaPerformanceAttributes,
     osu::OsuPerformanceAttributes,
@@ -10,13 +10,19 @@ use rosu_pp::{
 use serde::de;
 use wasm_bindgen::prelude::*;
 
-use crate::{score_state::JsScoreState, util::FieldVisitor};
+use crate::{
+    JsResult,
+    json::{self, JsJson, JsonObject},
+    score_state::JsScoreState,
+    util::{self, FieldVisitor},
+};
 
 use super::difficulty::JsDifficultyAttributes;
 
 /// The result of a performance calculation.
-#[wasm_bindgen(js_name = PerformanceAttributes, inspectable)]
-#[derive(Default)]
+#[wasm_bindgen(js_name = PerformanceAttributes)]
+#[derive(Default, serde::Deserialize)]
+#[serde(rename = "PerformanceAttributes", rename_all = "camelCase")]
 pub struct JsPerformanceAttributes {
     /// The difficulty attributes.
     #[wasm_bindgen(getter_with_clone, readonly)]
@@ -25,6 +31,7 @@ pub struct JsPerformanceAttributes {
     ///
     /// Only available if *not* created through gradual calculation.
     #[wasm_bindgen(getter_with_clone, readonly)]
+    #[serde(default, deserialize_with = "deserialize_state")]
     pub state: Option<JsScoreState>,
     /// The final performance points.
     #[wasm_bindgen(readonly)]
@@ -48,6 +55,7 @@ pub struct JsPerformanceAttributes {
     ///
     /// Only available for osu! and osu!taiko.
     #[wasm_bindgen(js_name = "ppAccuracy", readonly)]
+    #[serde(rename = "ppAccuracy")]
     pub pp_acc: Option<f64>,
     /// Scaled miss count based on total hits.
     ///
@@ -71,6 +79,52 @@ pub struct JsPerformanceAttributes {
     pub pp_difficulty: Option<f64>,
 }
 
+#[wasm_bindgen(js_class = PerformanceAttributes)]
+impl JsPerformanceAttributes {
+    /// Convert the attributes into a plain object that includes a schema
+    /// version.
+    ///
+    /// Unavailable attributes are omitted.
+    #[wasm_bindgen(js_name = toJSON)]
+    pub fn to_json(&self) -> JsJson {
+        let difficulty = JsonObject::new();
+        self.difficulty.json_fields(&difficulty);
+
+        let obj = JsonObject::versioned();
+        obj.set("difficulty", JsJson::from(difficulty));
+        obj.set_opt("state", self.state.clone());
+        obj.set("pp", self.pp);
+        obj.set_opt("ppAim", self.pp_aim);
+        obj.set_opt("ppFlashlight", self.pp_flashlight);
+        obj.set_opt("ppSpeed", self.pp_speed);
+        obj.set_opt("ppAccuracy", self.pp_acc);
+        obj.set_opt("effectiveMissCount", self.effective_miss_count);
+        obj.set_opt("estimatedUnstableRate", self.estimated_unstable_rate);
+        obj.set_opt("speedDeviation", self.speed_deviation);
+        obj.set_opt("ppDifficulty", self.pp_difficulty);
+
+        obj.into()
+    }
+
+    /// Stringified version of `PerformanceAttributes.toJSON`.
+    #[wasm_bindgen(js_name = toString)]
+    pub fn to_json_string(&self) -> String {
+        json::stringify(&self.to_json())
+    }
+
+    /// Create attributes from the result of `PerformanceAttributes.toJSON`.
+    /// @throws Throws an error if the JSON is invalid or of an unsupported schema version
+    #[wasm_bindgen(js_name = fromJSON)]
+    pub fn from_json(json: &JsJson) -> JsResult<JsPerformanceAttributes> {
+        json::check_version(json)?;
+        let mut attrs = util::from_value::<JsPerformanceAttributes>(json)?;
+        let difficulty = DifficultyAttributes::try_from(attrs.difficulty)?;
+        attrs.difficulty = difficulty.into();
+
+        Ok(attrs)
+    }
+}
+
 impl JsPerformanceAttributes {
     pub fn new(attrs: PerformanceAttributes, state: ScoreState) -> Self {
         let mut this = Self::from(attrs);
@@ -121,6 +175,12 @@ impl JsPerformanceAttributes {
     }
 }
 
+fn deserialize_state<'de, D: de::Deserializer<'de>>(
+    d: D,
+) -> Result<Option<JsScoreState>, D::Error> {
+    JsScoreState::deserialize(d).map(|state| Some(state.into()))
+}
+
 impl From<PerformanceAttributes> for JsPerformanceAttributes {
     fn from(attrs: PerformanceAttributes) -> Self {
         match attrs {
@@ -0,0 +1,267 @@
+use std::{error, fmt};
+
+use rosu_map::{
+    DecodeBeatmap, DecodeState,
+    section::{
+        events::{Events, EventsState, ParseEventsError},
+        metadata::{Metadata, ParseMetadataError},
+        timing_points::{ParseTimingPointsError, SamplePoint, TimingPoints, TimingPointsState},
+    },
+    util::{ParseNumber, StrExt},
+};
+use rosu_pp::{
+    Beatmap,
+    model::{
+        beatmap::{BeatmapState, ParseBeatmapError},
+        mode::GameMode,
+    },
+};
+
+use crate::hit_object::HitSamples;
+
+/// A [`Beatmap`] alongside `.osu` file data that `rosu-pp` does not retain.
+///
+/// All of it is gathered in a single pass over the file content.
+pub struct DecodedBeatmap {
+    pub map: Beatmap,
+    pub info: BeatmapInfo,
+}
+
+/// Data of a `.osu` file that is irrelevant for calculations.
+#[derive(Clone, Default)]
+pub struct BeatmapInfo {
+    pub metadata: Metadata,
+    pub audio_file: String,
+    pub preview_time: i32,
+    pub background_file: String,
+    pub sample_points: Vec<SamplePoint>,
+    /// Data for each hitobject of the [`Beatmap`].
+    ///
+    /// Empty if the hitobjects no longer correspond to the `.osu` file, e.g.
+    /// after converting to mania.
+    pub hit_objects: Vec<HitObjectInfo>,
+}
+
+/// Data of a hitobject that `rosu-pp` does not retain.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct HitObjectInfo {
+    pub new_combo: bool,
+    pub combo_offset: i32,
+    pub samples: HitSamples,
+}
+
+impl HitObjectInfo {
+    /// Parse a hitobject line that `rosu-pp` already accepted.
+    fn parse(line: &str) -> PendingHitObject {
+        const CIRCLE: i32 = 1 << 0;
+        const SLIDER: i32 = 1 << 1;
+        const NEW_COMBO: i32 = 1 << 2;
+        const SPINNER: i32 = 1 << 3;
+        const COMBO_OFFSET: i32 = (1 << 4) | (1 << 5) | (1 << 6);
+
+        let fields: Vec<_> = line.trim_comment().split(',').collect();
+        let field = |idx: usize| fields.get(idx).copied();
+
+        let x = field(0).map_or(0, |s| f32::parse(s).unwrap_or(0.0) as i32);
+        let start_time = field(2).map_or(0.0, |s| f64::parse(s).unwrap_or(0.0));
+        let kind = field(3).map_or(0, |s| s.parse_num::<i32>().unwrap_or(0));
+
+        let samples = if kind & CIRCLE > 0 {
+            field(5)
+        } else if kind & SLIDER > 0 {
+            field(10)
+        } else if kind & SPINNER > 0 {
+            field(6)
+        } else {
+            // Holds prefix the samples with their end time
+            field(5).and_then(|s| s.split_once(':')).map(|(_, s)| s)
+        };
+
+        let new_combo = kind & NEW_COMBO > 0;
+
+        let info = Self {
+            new_combo,
+            combo_offset: if new_combo {
+                (kind & COMBO_OFFSET) >> 4
+            } else {
+                0
+            },
+            samples: samples.map(HitSamples::parse).unwrap_or_default(),
+        };
+
+        PendingHitObject {
+            start_time,
+            x,
+            info,
+        }
+    }
+}
+
+/// A [`HitObjectInfo`] alongside the data to align it with `rosu-pp`'s
+/// hitobjects.
+struct PendingHitObject {
+    start_time: f64,
+    x: i32,
+    info: HitObjectInfo,
+}
+
+/// `rosu-pp` sorts mania hitobjects with osu!'s unstable legacy sort after
+/// stable sorting all hitobjects and hitsounds. Simultaneous hitobjects may
+/// thus be reordered without their hitsounds so both are realigned by
+/// matching x-coordinates.
+fn align_simultaneous(map: &mut Beatmap, pending: &mut [PendingHitObject]) {
+    if map.hit_objects.len() != pending.len() || map.hit_sounds.len() != pending.len() {
+        return;
+    }
+
+    let mut start = 0;
+
+    while start < pending.len() {
+        let time = pending[start].start_time;
+
+        let end = start
+            + pending[start..]
+                .iter()
+                .take_while(|h| h.start_time == time)
+                .count();
+
+        for i in start..end {
+            let x = map.hit_objects[i].pos.x as i32;
+
+            if let Some(j) = (i..end).find(|&j| pending[j].x == x) {
+                pending.swap(i, j);
+                map.hit_sounds.swap(i, j);
+            }
+        }
+
+        start = end;
+    }
+}
+
+pub struct DecodedBeatmapState {
+    map: BeatmapState,
+    timing_points: TimingPointsState,
+    metadata: Metadata,
+    events: EventsState,
+    hit_objects: Vec<PendingHitObject>,
+}
+
+impl DecodeState for DecodedBeatmapState {
+    fn create(version: i32) -> Self {
+        Self {
+            map: BeatmapState::create(version),
+            timing_points: TimingPointsState::create(version),
+            metadata: Metadata::create(version),
+            events: EventsState::create(version),
+            hit_objects: Vec::new(),
+        }
+    }
+}
+
+impl From<DecodedBeatmapState> for DecodedBeatmap {
+    fn from(mut state: DecodedBeatmapState) -> Self {
+        // Stable sort, same as `rosu-pp` does for hitobjects and hitsounds
+        state
+            .hit_objects
+            .sort_by(|a, b| a.start_time.total_cmp(&b.start_time));
+
+        let mut map = Beatmap::from(state.map);
+
+        if map.mode == GameMode::Mania {
+            align_simultaneous(&mut map, &mut state.hit_objects);
+        }
+
+        let timing_points = TimingPoints::from(state.timing_points);
+
+        let info = BeatmapInfo {
+            metadata: state.metadata,
+            audio_file: timing_points.audio_file,
+            preview_time: timing_points.preview_time,
+            background_file: state.events.background_file,
+            sample_points: timing_points.control_points.sample_points,
+            hit_objects: state.hit_objects.into_iter().map(|h| h.info).collect(),
+        };
+
+        Self { map, info }
+    }
+}
+
+macro_rules! parse_sections {
+    ( $( $fn:ident, )+ ) => {
+        $(
+            fn $fn(state: &mut Self::State, line: &str) -> Result<(), Self::Error> {
+                // Every state gets to see the line, even if a previous one
+                // failed to parse it.
+                let map = Beatmap::$fn(&mut state.map, line);
+                let timing_points = TimingPoints::$fn(&mut state.timing_points, line);
+                let metadata = Metadata::$fn(&mut state.metadata, line);
+                let events = Events::$fn(&mut state.events, line);
+
+                map.map_err(ParseDecodedBeatmapError::Beatmap)?;
+                timing_points.map_err(ParseDecodedBeatmapError::TimingPoints)?;
+                metadata.map_err(ParseDecodedBeatmapError::Metadata)?;
+                events.map_err(ParseDecodedBeatmapError::Events)
+            }
+        )*
+    };
+}
+
+impl DecodeBeatmap for DecodedBeatmap {
+    type Error = ParseDecodedBeatmapError;
+    type State = DecodedBeatmapState;
+
+    parse_sections! {
+        parse_general,
+        parse_editor,
+        parse_metadata,
+        parse_difficulty,
+        parse_events,
+        parse_timing_points,
+        parse_colors,
+        parse_variables,
+        parse_catch_the_beat,
+        parse_mania,
+    }
+
+    fn parse_hit_objects(state: &mut Self::State, line: &str) -> Result<(), Self::Error> {
+        Beatmap::parse_hit_objects(&mut state.map, line)
+            .map_err(ParseDecodedBeatmapError::Beatmap)?;
+
+        // Only keep data of hitobjects that made it into the beatmap. The
+        // other states ignore hitobjects.
+        state.hit_objects.push(HitObjectInfo::parse(line));
+
+        Ok(())
+    }
+}
+
+/// All the ways that parsing a line for [`DecodedBeatmap`] can fail.
+#[derive(Debug)]
+pub enum ParseDecodedBeatmapError {
+    Beatmap(ParseBeatmapError),
+    Events(ParseEventsError),
+    Metadata(ParseMetadataError),
+    TimingPoints(ParseTimingPointsError),
+}
+
+impl error::Error for ParseDecodedBeatmapError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::Beatmap(err) => Some(err),
+            Self::Events(err) => Some(err),
+            Self::Metadata(err) => Some(err),
+            Self::TimingPoints(err) => Some(err),
+        }
+    }
+}
+
+impl fmt::Display for ParseDecodedBeatmapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Beatmap(_) => f.write_str("failed to parse beatmap"),
+            Self::Events(_) => f.write_str("failed to parse events"),
+            Self::Metadata(_) => f.write_str("failed to parse metadata"),
+            Self::TimingPoints(_) => f.write_str("failed to parse timing points"),
+        }
+    }
+}
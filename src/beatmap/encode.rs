@@ -0,0 +1,379 @@
+use std::io::Result as IoResult;
+
+use rosu_map::{
+    LATEST_FORMAT_VERSION,
+    section::{
+        hit_objects::{
+            CurveBuffers, HitObject as MapHitObject, HitObjectCircle, HitObjectHold,
+            HitObjectKind as MapHitObjectKind, HitObjectSlider, HitObjectSpinner, SliderPath,
+            hit_samples::{HitSampleInfo, HitSoundType, SampleBank, SampleBankInfo},
+        },
+        timing_points::{
+            ControlPoints, DifficultyPoint as MapDifficultyPoint, EffectPoint as MapEffectPoint,
+            SamplePoint, TimingPoint as MapTimingPoint,
+        },
+    },
+};
+use rosu_pp::{
+    Beatmap,
+    model::hit_object::{HitObject, HitObjectKind},
+};
+
+use crate::hit_object::{self, HitSamples};
+
+use super::decode::{BeatmapInfo, HitObjectInfo};
+
+/// osu! looks up sample points slightly after a hitobject's nodes.
+const CONTROL_POINT_LENIENCY: f64 = 5.0;
+
+/// Encode a [`Beatmap`] into the content of a `.osu` file of the latest
+/// format version.
+pub fn encode(map: &Beatmap, info: &BeatmapInfo) -> IoResult<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(4096);
+    to_rosu_map(map, info).encode(&mut bytes)?;
+
+    Ok(bytes)
+}
+
+fn to_rosu_map(map: &Beatmap, info: &BeatmapInfo) -> rosu_map::Beatmap {
+    let control_points = ControlPoints {
+        timing_points: map
+            .timing_points
+            .iter()
+            .map(|point| MapTimingPoint {
+                time: point.time,
+                beat_len: point.beat_len,
+                ..Default::default()
+            })
+            .collect(),
+        difficulty_points: map
+            .difficulty_points
+            .iter()
+            .map(|point| MapDifficultyPoint {
+                time: point.time,
+                slider_velocity: point.slider_velocity,
+                generate_ticks: point.generate_ticks,
+            })
+            .collect(),
+        effect_points: map
+            .effect_points
+            .iter()
+            .map(|point| MapEffectPoint {
+                time: point.time,
+                kiai: point.kiai,
+                scroll_speed: point.scroll_speed,
+            })
+            .collect(),
+        sample_points: info.sample_points.clone(),
+    };
+
+    let mut bufs = CurveBuffers::default();
+
+    let hit_objects = map
+        .hit_objects
+        .iter()
+        .enumerate()
+        .map(|(i, h)| {
+            let hit_sound = map.hit_sounds.get(i).copied().unwrap_or_default();
+            let info = info.hit_objects.get(i);
+
+            to_rosu_map_hit_object(map, h, hit_sound, info, &control_points, &mut bufs)
+        })
+        .collect();
+
+    let metadata = info.metadata.clone();
+
+    rosu_map::Beatmap {
+        format_version: LATEST_FORMAT_VERSION,
+        audio_file: info.audio_file.clone(),
+        preview_time: info.preview_time,
+        stack_leniency: map.stack_leniency,
+        mode: map.mode,
+        title: metadata.title,
+        title_unicode: metadata.title_unicode,
+        artist: metadata.artist,
+        artist_unicode: metadata.artist_unicode,
+        creator: metadata.creator,
+        version: metadata.version,
+        source: metadata.source,
+        tags: metadata.tags,
+        beatmap_id: metadata.beatmap_id,
+        beatmap_set_id: metadata.beatmap_set_id,
+        hp_drain_rate: map.hp,
+        circle_size: map.cs,
+        overall_difficulty: map.od,
+        approach_rate: map.ar,
+        slider_multiplier: map.slider_multiplier,
+        slider_tick_rate: map.slider_tick_rate,
+        background_file: info.background_file.clone(),
+        breaks: map.breaks.clone(),
+        control_points,
+        hit_objects,
+        ..Default::default()
+    }
+}
+
+fn to_rosu_map_hit_object(
+    map: &Beatmap,
+    h: &HitObject,
+    hit_sound: HitSoundType,
+    info: Option<&HitObjectInfo>,
+    control_points: &ControlPoints,
+    bufs: &mut CurveBuffers,
+) -> MapHitObject {
+    let end_time = hit_object::end_time(map, h, bufs);
+
+    let (new_combo, combo_offset) =
+        info.map_or((false, 0), |info| (info.new_combo, info.combo_offset));
+
+    // Sliders only retain the sample banks, same as in osu!lazer
+    let bank_info = info.map_or_else(SampleBankInfo::default, |info| {
+        sample_bank_info(&info.samples, h.is_slider())
+    });
+
+    let kind = match h.kind {
+        HitObjectKind::Circle => MapHitObjectKind::Circle(HitObjectCircle {
+            pos: h.pos,
+            new_combo,
+            combo_offset,
+        }),
+        HitObjectKind::Slider(ref slider) => {
+            let span_duration = (end_time - h.start_time) / slider.span_count() as f64;
+
+            let node_samples = slider
+                .node_sounds
+                .iter()
+                .enumerate()
+                .map(|(i, sound)| {
+                    let time = h.start_time + i as f64 * span_duration;
+
+                    samples(*sound, bank_info.clone(), time, control_points)
+                })
+                .collect();
+
+            MapHitObjectKind::Slider(HitObjectSlider {
+                pos: h.pos,
+                new_combo,
+                combo_offset,
+                path: SliderPath::new(
+                    map.mode,
+                    slider.control_points.to_vec(),
+                    slider.expected_dist,
+                ),
+                node_samples,
+                repeat_count: slider.repeats as i32,
+                velocity: hit_object::slider_velocity(map, h.start_time),
+            })
+        }
+        HitObjectKind::Spinner(ref spinner) => MapHitObjectKind::Spinner(HitObjectSpinner {
+            pos: h.pos,
+            duration: spinner.duration,
+            new_combo,
+        }),
+        HitObjectKind::Hold(ref hold) => MapHitObjectKind::Hold(HitObjectHold {
+            pos_x: h.pos.x,
+            duration: hold.duration,
+        }),
+    };
+
+    MapHitObject {
+        start_time: h.start_time,
+        kind,
+        samples: samples(hit_sound, bank_info, end_time, control_points),
+    }
+}
+
+/// The sample banks of a hitobject, read the same way as `rosu-map` does.
+fn sample_bank_info(samples: &HitSamples, banks_only: bool) -> SampleBankInfo {
+    let bank = |set: i32| match SampleBank::try_from(set).unwrap_or(SampleBank::Normal) {
+        SampleBank::None => None,
+        bank => Some(bank),
+    };
+
+    let bank_for_normal = bank(samples.normal_set);
+
+    let mut bank_info = SampleBankInfo {
+        bank_for_normal,
+        bank_for_addition: bank(samples.addition_set).or(bank_for_normal),
+        ..Default::default()
+    };
+
+    if !banks_only {
+        bank_info.custom_sample_bank = samples.index;
+        bank_info.volume = samples.volume;
+        bank_info.filename = Some(samples.filename.clone()).filter(|name| !name.is_empty());
+    }
+
+    bank_info
+}
+
+/// The samples of a hitobject node where unspecified values are taken from the
+/// active sample point.
+fn samples(
+    hit_sound: HitSoundType,
+    bank_info: SampleBankInfo,
+    time: f64,
+    control_points: &ControlPoints,
+) -> Vec<HitSampleInfo> {
+    let sample_point = control_points
+        .sample_point_at(time + CONTROL_POINT_LENIENCY)
+        .map_or_else(SamplePoint::default, SamplePoint::clone);
+
+    let mut samples = bank_info.convert_sound_type(hit_sound);
+
+    for sample in samples.iter_mut() {
+        sample_point.apply(sample);
+    }
+
+    samples
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::beatmap::decode::DecodedBeatmap;
+
+    use super::*;
+
+    const OSU: &str = "osu file format v14
+
+[General]
+Mode: 0
+
+[Difficulty]
+HPDrainRate:5
+CircleSize:4
+OverallDifficulty:8
+ApproachRate:9
+SliderMultiplier:1.4
+SliderTickRate:1
+
+[TimingPoints]
+0,500,4,2,0,60,1,0
+1500,-50,4,1,0,80,0,0
+
+[HitObjects]
+256,192,0,5,0,0:0:0:0:
+100,100,500,1,2,2:3:0:0:
+200,100,750,37,8,1:0:0:0:
+300,300,1000,2,0,B|350:300|400:250,2,120,2|0|8,1:2|0:0|3:0,3:1:0:0:
+256,192,2000,12,0,3000,0:0:0:0:
+128,64,3500,1,4,0:0:0:0:hit.wav
+";
+
+    const MANIA: &str = "osu file format v14
+
+[General]
+Mode: 3
+
+[Difficulty]
+HPDrainRate:8
+CircleSize:4
+OverallDifficulty:8
+ApproachRate:5
+SliderMultiplier:1.4
+SliderTickRate:1
+
+[TimingPoints]
+0,400,4,2,0,60,1,0
+
+[HitObjects]
+64,192,0,1,0,1:2:3:40:
+192,192,200,128,2,600:2:0:0:70:
+320,192,400,1,8,0:0:0:0:drum.wav
+448,192,400,128,0,800:0:0:0:0:
+";
+
+    fn round_trip(content: &str) {
+        let decoded = rosu_map::from_bytes::<DecodedBeatmap>(content.as_bytes()).unwrap();
+        let bytes = encode(&decoded.map, &decoded.info).unwrap();
+        let encoded = rosu_map::from_bytes::<DecodedBeatmap>(&bytes).unwrap();
+
+        assert_eq!(
+            decoded.map.hit_objects.len(),
+            decoded.info.hit_objects.len()
+        );
+        assert_eq!(hit_objects(&decoded), hit_objects(&encoded));
+    }
+
+    /// Hitobjects alongside their hitsound and data that is retained on
+    /// encoding.
+    ///
+    /// Sorted because mania's legacy sort may reorder simultaneous
+    /// hitobjects. Sample sets are not compared because encoding resolves
+    /// them against sample points.
+    fn hit_objects(decoded: &DecodedBeatmap) -> Vec<(HitObject, HitSoundType, bool, i32, String)> {
+        let mut hit_objects: Vec<_> = decoded
+            .map
+            .hit_objects
+            .iter()
+            .zip(&decoded.map.hit_sounds)
+            .zip(&decoded.info.hit_objects)
+            .map(|((h, sound), info)| {
+                (
+                    h.clone(),
+                    *sound,
+                    info.new_combo,
+                    info.combo_offset,
+                    info.samples.filename.clone(),
+                )
+            })
+            .collect();
+
+        hit_objects.sort_by(|(a, ..), (b, ..)| {
+            a.start_time
+                .total_cmp(&b.start_time)
+                .then(a.pos.x.total_cmp(&b.pos.x))
+        });
+
+        hit_objects
+    }
+
+    #[test]
+    fn round_trip_osu() {
+        round_trip(OSU);
+    }
+
+    #[test]
+    fn round_trip_mania() {
+        round_trip(MANIA);
+    }
+
+    #[test]
+    fn mania_simultaneous_objects() {
+        let decoded = rosu_map::from_bytes::<DecodedBeatmap>(MANIA.as_bytes()).unwrap();
+
+        let idx = decoded
+            .map
+            .hit_objects
+            .iter()
+            .position(|h| h.pos.x == 320.0)
+            .unwrap();
+
+        assert_eq!(u8::from(decoded.map.hit_sounds[idx]), 8);
+        assert_eq!(decoded.info.hit_objects[idx].samples.filename, "drum.wav");
+    }
+
+    #[test]
+    fn new_combos() {
+        let decoded = rosu_map::from_bytes::<DecodedBeatmap>(OSU.as_bytes()).unwrap();
+
+        let new_combos: Vec<_> = decoded
+            .info
+            .hit_objects
+            .iter()
+            .map(|h| (h.new_combo, h.combo_offset))
+            .collect();
+
+        assert_eq!(
+            new_combos,
+            [
+                (true, 0),
+                (false, 0),
+                (true, 2),
+                (false, 0),
+                (true, 0),
+                (false, 0),
+            ]
+        );
+    }
+}
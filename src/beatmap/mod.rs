@@ -0,0 +1,600 @@
+use std::{
+    error,
+    fmt::{Formatter, Result as FmtResult, Write},
+};
+
+use rosu_map::section::hit_objects::CurveBuffers;
+use rosu_pp::{
+    Beatmap,
+    model::{
+        control_point::TimingPoint,
+        hit_object::{HitObjectKind, HitSoundType},
+        mode::GameMode,
+    },
+};
+use serde::de;
+use sha2::{Digest, Sha256};
+use wasm_bindgen::{__rt::RcRef, JsCast, convert::RefFromWasmAbi, prelude::wasm_bindgen};
+
+use crate::{
+    JsError, JsResult,
+    args::beatmap::{BeatmapContent, JsBeatmapContent},
+    control_point::{JsDifficultyPoint, JsEffectPoint, JsSamplePoint, JsTimingPoint},
+    deserializer::JsDeserializer,
+    hit_object::{self, HitSamples, JsHitObject, JsHitObjectKind},
+    mode::JsGameMode,
+    mods::JsGameMods,
+    util::{self, FieldVisitor},
+};
+
+use self::{
+    decode::{BeatmapInfo, DecodedBeatmap},
+    suspicion::{JsSuspicionReport, SuspicionReport},
+};
+
+mod decode;
+mod encode;
+mod slice;
+mod suspicion;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "Beatmap[]")]
+    pub type JsBeatmapList;
+}
+
+/// All beatmap data that is relevant for difficulty and performance
+/// calculation.
+///
+/// It is recommended to call the method `Beatmap.free` on instances that are
+/// no longer in use to avoid the risk of leaking memory.
+#[wasm_bindgen(js_name = Beatmap)]
+pub struct JsBeatmap {
+    pub(crate) inner: Beatmap,
+    info: BeatmapInfo,
+    /// SHA-256 digest that identifies the beatmap's content, including
+    /// conversions and slices.
+    pub(crate) hash: [u8; 32],
+}
+
+#[wasm_bindgen(js_class = Beatmap)]
+impl JsBeatmap {
+    /// Create a new beatmap instance by parsing an `.osu` file's content.
+    /// @throws Throws an error if decoding the map failed
+    #[wasm_bindgen(constructor)]
+    pub fn new(args: &JsBeatmapContent) -> JsResult<JsBeatmap> {
+        let content = util::from_value::<BeatmapContent>(args)?;
+
+        match rosu_map::from_bytes::<DecodedBeatmap>(&content.bytes) {
+            Ok(decoded) => Ok(Self {
+                inner: decoded.map,
+                info: decoded.info,
+                hash: Sha256::digest(&content.bytes).into(),
+            }),
+            Err(err) => {
+                let mut e = &err as &dyn error::Error;
+                let mut content = format!("Failed to decode beatmap: {e}");
+
+                while let Some(src) = e.source() {
+                    let _ = writeln!(content, "  - caused by: {src}");
+                    e = src;
+                }
+
+                Err(JsError::new(&content))
+            }
+        }
+    }
+
+    /// Convert a beatmap to a specific mode.
+    /// @throws Throws an error if conversion fails or mods are invalid
+    pub fn convert(&mut self, mode: JsGameMode, mods: Option<JsGameMods>) -> JsResult<()> {
+        let mods = mods
+            .as_deref()
+            .map(JsDeserializer::from_ref)
+            .map(util::deserialize_mods)
+            .transpose()?
+            .unwrap_or_default();
+
+        let mode = GameMode::from(mode);
+        let mods_key = serde_json::to_string(&mods).unwrap_or_default();
+
+        let n_objects = self.inner.hit_objects.len();
+
+        if let Err(err) = self.inner.convert_mut(mode, &mods.into()) {
+            return Err(JsError::new(&err.to_string()));
+        }
+
+        // Mania conversion generates entirely new hitobjects and taiko
+        // conversion may split sliders so hitobject data no longer applies
+        if mode == GameMode::Mania || self.inner.hit_objects.len() != n_objects {
+            self.info.hit_objects.clear();
+        }
+
+        self.hash = self.derive_hash(|hasher| {
+            hasher.update([mode as u8]);
+            hasher.update(mods_key);
+        });
+
+        Ok(())
+    }
+
+    /// Encode the beatmap into the content of a `.osu` file of format
+    /// version 14.
+    ///
+    /// Changes through `Beatmap.convert` are included but since `.osu` files
+    /// cannot mark a beatmap as converted, decoding the result will yield
+    /// `isConvert = false`. New combos and custom sample info of hitobjects
+    /// are kept, although custom sample indices and volumes are moved into
+    /// sample points unless the beatmap is osu!mania. Data that is not
+    /// retained on decoding such as combo colors or storyboard events is
+    /// omitted.
+    /// @throws Throws an error if encoding fails
+    #[wasm_bindgen(js_name = toOsuString)]
+    pub fn to_osu_string(&self) -> JsResult<String> {
+        self.encode()
+            .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+    }
+
+    /// Encode the beatmap into the content of a `.osu` file of format
+    /// version 14 as bytes.
+    ///
+    /// See `Beatmap.toOsuString`.
+    /// @throws Throws an error if encoding fails
+    #[wasm_bindgen(js_name = toBytes)]
+    pub fn to_bytes(&self) -> JsResult<Vec<u8>> {
+        self.encode()
+    }
+
+    /// Create a new beatmap that only contains the hitobjects whose start time
+    /// in milliseconds is within `startTime` (inclusive) and `endTime`
+    /// (exclusive).
+    ///
+    /// Breaks and control points are trimmed to the range. Control points
+    /// that are active at `startTime` are retained and hitobjects are not
+    /// shifted in time.
+    pub fn slice(&self, start_time: f64, end_time: f64) -> JsBeatmap {
+        let (inner, info) = slice::slice_by_time(&self.inner, &self.info, start_time, end_time);
+
+        let hash = self.derive_hash(|hasher| {
+            hasher.update("slice");
+            hasher.update(start_time.to_le_bytes());
+            hasher.update(end_time.to_le_bytes());
+        });
+
+        Self { inner, info, hash }
+    }
+
+    /// Create a new beatmap that only contains the hitobjects with an index
+    /// between `start` (inclusive) and `end` (exclusive).
+    ///
+    /// Breaks and control points are trimmed to the time range of the
+    /// hitobjects, see `Beatmap.slice`.
+    #[wasm_bindgen(js_name = sliceObjects)]
+    pub fn slice_objects(&self, start: usize, end: usize) -> JsBeatmap {
+        let (inner, info) = slice::slice_by_index(&self.inner, &self.info, start, end);
+
+        let hash = self.derive_hash(|hasher| {
+            hasher.update("sliceObjects");
+            hasher.update((start as u64).to_le_bytes());
+            hasher.update((end as u64).to_le_bytes());
+        });
+
+        Self { inner, info, hash }
+    }
+
+    /// Check whether hitobjects appear too suspicious for further calculation.
+    ///
+    /// Sometimes a beatmap isn't created for gameplay but rather to test
+    /// the limits of osu! itself. Difficulty- and/or performance calculation
+    /// should likely be avoided on these maps due to potential performance
+    /// issues.
+    ///
+    /// See `Beatmap.checkSuspicion` for the reason why a beatmap is
+    /// suspicious.
+    #[wasm_bindgen(js_name = isSuspicious)]
+    pub fn is_suspicious(&self) -> bool {
+        self.inner.check_suspicion().is_err()
+    }
+
+    /// Check whether hitobjects appear too suspicious for further calculation
+    /// and if so, report why.
+    ///
+    /// Returns `undefined` if the beatmap is not suspicious. Otherwise, the
+    /// report contains the kind of suspicion as well as the offending values
+    /// and hitobjects which can help deciding whether to skip or throttle
+    /// calculations on the beatmap.
+    #[wasm_bindgen(js_name = checkSuspicion)]
+    pub fn check_suspicion(&self) -> Option<JsSuspicionReport> {
+        let suspicion = self.inner.check_suspicion().err()?;
+
+        Some(SuspicionReport::new(&self.inner, &suspicion).into())
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn bpm(&self) -> f64 {
+        self.inner.bpm()
+    }
+
+    /// The online ID of the beatmap; `-1` if unavailable.
+    #[wasm_bindgen(js_name = beatmapId, getter)]
+    pub fn beatmap_id(&self) -> i32 {
+        self.info.metadata.beatmap_id
+    }
+
+    /// The online ID of the beatmap's mapset; `0` if unavailable.
+    #[wasm_bindgen(js_name = beatmapSetId, getter)]
+    pub fn beatmap_set_id(&self) -> i32 {
+        self.info.metadata.beatmap_set_id
+    }
+
+    /// The filename of the audio file.
+    #[wasm_bindgen(js_name = audioFile, getter)]
+    pub fn audio_file(&self) -> String {
+        self.info.audio_file.clone()
+    }
+
+    /// The start time of the song preview in milliseconds; `-1` if
+    /// unspecified.
+    #[wasm_bindgen(js_name = previewTime, getter)]
+    pub fn preview_time(&self) -> i32 {
+        self.info.preview_time
+    }
+
+    /// The filename of the background image.
+    #[wasm_bindgen(js_name = backgroundFile, getter)]
+    pub fn background_file(&self) -> String {
+        self.info.background_file.clone()
+    }
+
+    /// The beats per minute of the timing point that lasts the longest.
+    ///
+    /// Same as `bpm`.
+    #[wasm_bindgen(js_name = mostCommonBpm, getter)]
+    pub fn most_common_bpm(&self) -> f64 {
+        self.inner.bpm()
+    }
+
+    /// The lowest beats per minute across all timing points.
+    #[wasm_bindgen(js_name = minBpm, getter)]
+    pub fn min_bpm(&self) -> f64 {
+        self.inner
+            .timing_points
+            .iter()
+            .map(TimingPoint::bpm)
+            .min_by(f64::total_cmp)
+            .unwrap_or(TimingPoint::DEFAULT_BPM)
+    }
+
+    /// The highest beats per minute across all timing points.
+    #[wasm_bindgen(js_name = maxBpm, getter)]
+    pub fn max_bpm(&self) -> f64 {
+        self.inner
+            .timing_points
+            .iter()
+            .map(TimingPoint::bpm)
+            .max_by(f64::total_cmp)
+            .unwrap_or(TimingPoint::DEFAULT_BPM)
+    }
+
+    /// The beats per minute at the given time in milliseconds.
+    #[wasm_bindgen(js_name = bpmAt)]
+    pub fn bpm_at(&self, time: f64) -> f64 {
+        let points = &self.inner.timing_points;
+
+        hit_object::point_at(points, time, |point| point.time)
+            .or_else(|| points.first())
+            .map_or(TimingPoint::DEFAULT_BPM, TimingPoint::bpm)
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn mode(&self) -> JsGameMode {
+        JsGameMode::from(self.inner.mode)
+    }
+
+    #[wasm_bindgen(js_name = nBreaks, getter)]
+    pub fn n_breaks(&self) -> usize {
+        self.inner.breaks.len()
+    }
+
+    #[wasm_bindgen(js_name = nObjects, getter)]
+    pub fn n_objects(&self) -> usize {
+        self.inner.hit_objects.len()
+    }
+
+    #[wasm_bindgen(js_name = nCircles, getter)]
+    pub fn n_circles(&self) -> usize {
+        self.inner
+            .hit_objects
+            .iter()
+            .filter(|h| h.is_circle())
+            .count()
+    }
+
+    #[wasm_bindgen(js_name = nSliders, getter)]
+    pub fn n_sliders(&self) -> usize {
+        self.inner
+            .hit_objects
+            .iter()
+            .filter(|h| h.is_slider())
+            .count()
+    }
+
+    #[wasm_bindgen(js_name = nSpinners, getter)]
+    pub fn n_spinners(&self) -> usize {
+        self.inner
+            .hit_objects
+            .iter()
+            .filter(|h| h.is_spinner())
+            .count()
+    }
+
+    #[wasm_bindgen(js_name = nHolds, getter)]
+    pub fn n_holds(&self) -> usize {
+        self.inner
+            .hit_objects
+            .iter()
+            .filter(|h| matches!(h.kind, HitObjectKind::Hold(_)))
+            .count()
+    }
+
+    /// All hitobjects of the beatmap.
+    ///
+    /// For large maps, prefer the typed array methods like
+    /// `hitObjectStartTimes` which avoid creating an object per hitobject.
+    #[wasm_bindgen(js_name = hitObjects)]
+    pub fn hit_objects(&self) -> Vec<JsHitObject> {
+        let mut bufs = CurveBuffers::default();
+
+        self.inner
+            .hit_objects
+            .iter()
+            .enumerate()
+            .map(|(i, h)| {
+                JsHitObject::new(
+                    &self.inner,
+                    h,
+                    self.hit_sound(i),
+                    self.hit_samples(i),
+                    &mut bufs,
+                )
+            })
+            .collect()
+    }
+
+    /// The hitobject at the given index.
+    #[wasm_bindgen(js_name = hitObjectAt)]
+    pub fn hit_object_at(&self, idx: usize) -> Option<JsHitObject> {
+        let h = self.inner.hit_objects.get(idx)?;
+        let mut bufs = CurveBuffers::default();
+
+        Some(JsHitObject::new(
+            &self.inner,
+            h,
+            self.hit_sound(idx),
+            self.hit_samples(idx),
+            &mut bufs,
+        ))
+    }
+
+    /// The kind of each hitobject.
+    #[wasm_bindgen(js_name = hitObjectKinds)]
+    pub fn hit_object_kinds(&self) -> Vec<u8> {
+        self.inner
+            .hit_objects
+            .iter()
+            .map(|h| JsHitObjectKind::from(&h.kind) as u8)
+            .collect()
+    }
+
+    /// The start time of each hitobject.
+    #[wasm_bindgen(js_name = hitObjectStartTimes)]
+    pub fn hit_object_start_times(&self) -> Vec<f64> {
+        self.inner
+            .hit_objects
+            .iter()
+            .map(|h| h.start_time)
+            .collect()
+    }
+
+    /// The end time of each hitobject.
+    #[wasm_bindgen(js_name = hitObjectEndTimes)]
+    pub fn hit_object_end_times(&self) -> Vec<f64> {
+        let mut bufs = CurveBuffers::default();
+
+        self.inner
+            .hit_objects
+            .iter()
+            .map(|h| hit_object::end_time(&self.inner, h, &mut bufs))
+            .collect()
+    }
+
+    /// The position of each hitobject, interleaved as `[x0, y0, x1, y1, ...]`.
+    #[wasm_bindgen(js_name = hitObjectPositions)]
+    pub fn hit_object_positions(&self) -> Vec<f32> {
+        self.inner
+            .hit_objects
+            .iter()
+            .flat_map(|h| [h.pos.x, h.pos.y])
+            .collect()
+    }
+
+    /// The hitsound bitflags of each hitobject.
+    #[wasm_bindgen(js_name = hitObjectHitSounds)]
+    pub fn hit_object_hit_sounds(&self) -> Vec<u8> {
+        (0..self.inner.hit_objects.len())
+            .map(|i| u8::from(self.hit_sound(i)))
+            .collect()
+    }
+
+    /// All uninherited timing points of the beatmap.
+    #[wasm_bindgen(js_name = timingPoints)]
+    pub fn timing_points(&self) -> Vec<JsTimingPoint> {
+        self.inner
+            .timing_points
+            .iter()
+            .map(JsTimingPoint::from)
+            .collect()
+    }
+
+    /// All difficulty points of the beatmap, including slider velocity changes.
+    #[wasm_bindgen(js_name = difficultyPoints)]
+    pub fn difficulty_points(&self) -> Vec<JsDifficultyPoint> {
+        self.inner
+            .difficulty_points
+            .iter()
+            .map(JsDifficultyPoint::from)
+            .collect()
+    }
+
+    /// All effect points of the beatmap, including kiai sections.
+    #[wasm_bindgen(js_name = effectPoints)]
+    pub fn effect_points(&self) -> Vec<JsEffectPoint> {
+        self.inner
+            .effect_points
+            .iter()
+            .map(JsEffectPoint::from)
+            .collect()
+    }
+
+    /// All sample points of the beatmap.
+    #[wasm_bindgen(js_name = samplePoints)]
+    pub fn sample_points(&self) -> Vec<JsSamplePoint> {
+        self.info
+            .sample_points
+            .iter()
+            .map(JsSamplePoint::from)
+            .collect()
+    }
+}
+
+impl JsBeatmap {
+    fn encode(&self) -> JsResult<Vec<u8>> {
+        encode::encode(&self.inner, &self.info)
+            .map_err(|err| JsError::new(&format!("Failed to encode beatmap: {err}")))
+    }
+
+    /// Combines the current hash with data of a modification.
+    fn derive_hash(&self, f: impl FnOnce(&mut Sha256)) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(self.hash);
+        f(&mut hasher);
+
+        hasher.finalize().into()
+    }
+
+    fn hit_sound(&self, idx: usize) -> HitSoundType {
+        self.inner.hit_sounds.get(idx).copied().unwrap_or_default()
+    }
+
+    fn hit_samples(&self, idx: usize) -> Option<&HitSamples> {
+        self.info.hit_objects.get(idx).map(|h| &h.samples)
+    }
+
+    /// Borrow each beatmap of the list.
+    pub fn from_list(list: &JsBeatmapList) -> JsResult<Vec<RcRef<Self>>> {
+        let Some(array) = list.dyn_ref::<js_sys::Array>() else {
+            return Err(JsError::new("Expected a list of beatmaps"));
+        };
+
+        array
+            .iter()
+            .map(|value| Self::deserialize(JsDeserializer::from_ref(&value)))
+            .collect()
+    }
+
+    pub fn deserialize<'de, D: de::Deserializer<'de>>(d: D) -> Result<RcRef<Self>, D::Error> {
+        struct BeatmapField;
+
+        impl BeatmapField {
+            const NAME: &'static str = "__wbg_ptr";
+        }
+
+        impl<'de> de::Deserialize<'de> for BeatmapField {
+            fn deserialize<D: de::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+                d.deserialize_str(FieldVisitor::new(Self::NAME))
+                    .map(|_| Self)
+            }
+        }
+
+        struct BeatmapVisitor;
+
+        impl<'de> de::Visitor<'de> for BeatmapVisitor {
+            type Value = RcRef<JsBeatmap>;
+
+            fn expecting(&self, f: &mut Formatter) -> FmtResult {
+                f.write_str("a Beatmap")
+            }
+
+            fn visit_map<A: de::MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                if map.next_key::<BeatmapField>()?.is_none() {
+                    return Err(de::Error::custom("expected a Beatmap"));
+                }
+
+                let ptr_u32 = map.next_value::<u32>()?;
+                let instance_ref = unsafe { JsBeatmap::ref_from_abi(ptr_u32) };
+
+                Ok(instance_ref)
+            }
+        }
+
+        d.deserialize_struct("Beatmap", &[BeatmapField::NAME], BeatmapVisitor)
+    }
+}
+
+macro_rules! beatmap_getters {
+    ( $( $field:ident as $getter:ident: $ty:ty, )+ ) => {
+        #[wasm_bindgen(js_class = Beatmap)]
+        impl JsBeatmap {
+            $(
+                #[wasm_bindgen(js_name = $getter, getter)]
+                pub fn $field(&self) -> $ty {
+                    self.inner.$field
+                }
+            )*
+        }
+    };
+}
+
+macro_rules! metadata_getters {
+    ( $( $( #[$meta:meta] )* $fn:ident($field:ident) as $getter:ident, )+ ) => {
+        #[wasm_bindgen(js_class = Beatmap)]
+        impl JsBeatmap {
+            $(
+                $( #[$meta] )*
+                #[wasm_bindgen(js_name = $getter, getter)]
+                pub fn $fn(&self) -> String {
+                    self.info.metadata.$field.clone()
+                }
+            )*
+        }
+    };
+}
+
+metadata_getters! {
+    title(title) as title,
+    title_unicode(title_unicode) as titleUnicode,
+    artist(artist) as artist,
+    artist_unicode(artist_unicode) as artistUnicode,
+    /// The name of the beatmap's mapper.
+    creator(creator) as creator,
+    /// The name of the difficulty.
+    ///
+    /// Not to be confused with `version` which is the `.osu` file format
+    /// version.
+    difficulty_name(version) as difficultyName,
+    source(source) as source,
+    /// Space-separated search tags.
+    tags(tags) as tags,
+}
+
+beatmap_getters! {
+    version as version: i32,
+    is_convert as isConvert: bool,
+    stack_leniency as stackLeniency: f32,
+    ar as ar: f32,
+    cs as cs: f32,
+    hp as hp: f32,
+    od as od: f32,
+    slider_multiplier as sliderMultiplier: f64,
+    slider_tick_rate as sliderTickRate: f64,
+}
@@ -0,0 +1,137 @@
+use std::ops::Range;
+
+use rosu_map::section::hit_objects::CurveBuffers;
+use rosu_pp::{Beatmap, model::beatmap::BreakPeriod};
+
+use crate::hit_object;
+
+use super::decode::BeatmapInfo;
+
+/// Create a copy of a [`Beatmap`] that only contains the hitobjects whose
+/// start time is within the given time range.
+pub fn slice_by_time(
+    map: &Beatmap,
+    info: &BeatmapInfo,
+    start_time: f64,
+    end_time: f64,
+) -> (Beatmap, BeatmapInfo) {
+    let objects = &map.hit_objects;
+    let start = objects.partition_point(|h| h.start_time < start_time);
+    let end = objects
+        .partition_point(|h| h.start_time < end_time)
+        .max(start);
+
+    slice(map, info, start..end, start_time, end_time, false)
+}
+
+/// Create a copy of a [`Beatmap`] that only contains the hitobjects within
+/// the given index range.
+pub fn slice_by_index(
+    map: &Beatmap,
+    info: &BeatmapInfo,
+    start: usize,
+    end: usize,
+) -> (Beatmap, BeatmapInfo) {
+    let end = end.min(map.hit_objects.len());
+    let start = start.min(end);
+
+    let objects = &map.hit_objects[start..end];
+    let mut bufs = CurveBuffers::default();
+
+    let start_time = objects.first().map_or(0.0, |h| h.start_time);
+
+    let end_time = objects
+        .iter()
+        .map(|h| hit_object::end_time(map, h, &mut bufs))
+        .fold(start_time, f64::max);
+
+    // The last hitobject's end needs to be included
+    slice(map, info, start..end, start_time, end_time, true)
+}
+
+fn slice(
+    map: &Beatmap,
+    info: &BeatmapInfo,
+    objects: Range<usize>,
+    start_time: f64,
+    end_time: f64,
+    end_inclusive: bool,
+) -> (Beatmap, BeatmapInfo) {
+    let before_end = |time: f64| time < end_time || (end_inclusive && time == end_time);
+
+    let breaks = map
+        .breaks
+        .iter()
+        .filter(|b| b.end_time > start_time && before_end(b.start_time))
+        .map(|b| BreakPeriod {
+            start_time: b.start_time.max(start_time),
+            end_time: b.end_time.min(end_time),
+        })
+        .collect();
+
+    let hit_sounds = map
+        .hit_sounds
+        .get(objects.clone())
+        .map_or_else(Vec::new, <[_]>::to_vec);
+
+    let sliced_map = Beatmap {
+        version: map.version,
+        is_convert: map.is_convert,
+        stack_leniency: map.stack_leniency,
+        mode: map.mode,
+        ar: map.ar,
+        cs: map.cs,
+        hp: map.hp,
+        od: map.od,
+        slider_multiplier: map.slider_multiplier,
+        slider_tick_rate: map.slider_tick_rate,
+        breaks,
+        timing_points: control_points(&map.timing_points, start_time, before_end, |p| p.time),
+        difficulty_points: control_points(&map.difficulty_points, start_time, before_end, |p| {
+            p.time
+        }),
+        effect_points: control_points(&map.effect_points, start_time, before_end, |p| p.time),
+        hit_objects: map.hit_objects[objects.clone()].to_vec(),
+        hit_sounds,
+    };
+
+    let sliced_info = BeatmapInfo {
+        metadata: info.metadata.clone(),
+        audio_file: info.audio_file.clone(),
+        preview_time: info.preview_time,
+        background_file: info.background_file.clone(),
+        sample_points: control_points(&info.sample_points, start_time, before_end, |p| p.time),
+        hit_objects: info
+            .hit_objects
+            .get(objects)
+            .map_or_else(Vec::new, <[_]>::to_vec),
+    };
+
+    (sliced_map, sliced_info)
+}
+
+/// Keep the control point that is active at `start_time` as well as all
+/// control points within the time range.
+fn control_points<T: Clone>(
+    points: &[T],
+    start_time: f64,
+    before_end: impl Fn(f64) -> bool,
+    get_time: impl Fn(&T) -> f64,
+) -> Vec<T> {
+    let start = points
+        .partition_point(|p| get_time(p) <= start_time)
+        .saturating_sub(1);
+
+    let end = points
+        .partition_point(|p| before_end(get_time(p)))
+        .max(start);
+
+    let mut sliced = points[start..end].to_vec();
+
+    // Retain the first timing point even if the range precedes it
+    if sliced.is_empty() {
+        sliced.extend(points.first().cloned());
+    }
+
+    sliced
+}
@@ -0,0 +1,308 @@
+use std::cmp;
+
+use rosu_pp::{
+    Beatmap,
+    model::{
+        beatmap::TooSuspicious,
+        hit_object::{HitObject, HitObjectKind},
+        mode::GameMode,
+    },
+};
+use wasm_bindgen::{JsCast, JsValue, prelude::wasm_bindgen};
+
+use crate::util;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = SuspicionReport)]
+    pub type JsSuspicionReport;
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const _: &'static str = r#"/**
+* Why a beatmap appears too suspicious for further calculation.
+*/
+export interface SuspicionReport {
+    /**
+    * The kind of suspicion.
+    *
+    * - `objectCount`: The beatmap has too many hitobjects.
+    * - `length`: The time between the first and last hitobject is too long.
+    * - `density`: Too many hitobjects appear within a short time.
+    * - `redFlag`: A slider has both an absurd amount of repeats and an
+    *   absurd position.
+    * - `sliderPositions`: Too many sliders have absurd positions.
+    * - `sliderRepeats`: Too many sliders have an absurd amount of repeats.
+    * - `unknown`: A kind that is not covered yet.
+    */
+    reason: "objectCount" | "length" | "density" | "redFlag" | "sliderPositions" | "sliderRepeats" | "unknown";
+    /**
+    * The offending value, i.e. the amount of hitobjects for `objectCount`
+    * and `density`, the time in ms for `length`, and the amount of
+    * offending sliders otherwise.
+    */
+    value: number;
+    /**
+    * The threshold that `value` exceeds.
+    */
+    threshold: number;
+    /**
+    * Start time in ms of the first offending hitobject.
+    */
+    startTime?: number;
+    /**
+    * Start time in ms of the last offending hitobject.
+    */
+    endTime?: number;
+    /**
+    * Indices of the offending hitobjects.
+    *
+    * Empty for `objectCount` and `unknown`.
+    */
+    objects: Uint32Array;
+    /**
+    * Human-readable description of the suspicion.
+    */
+    message: string;
+}"#;
+
+// The thresholds mirror the heuristic of `Beatmap::check_suspicion`.
+
+const MAX_OBJECTS: usize = 500_000;
+const MAX_OBJECTS_TAIKO: usize = 20_000;
+
+const MAX_LENGTH: f64 = (60 * 60 * 24 * 1000) as f64;
+
+/// Amount of hitobjects per 1s and 10s; scaled up for osu!taiko and
+/// osu!mania.
+const MAX_DENSITY: [(usize, f64); 2] = [(200, 1000.0), (500, 10_000.0)];
+
+const MAX_SLIDER_POS: f32 = 10_000.0;
+const MAX_SLIDER_REPEATS: usize = 1000;
+const MAX_SUSPICIOUS_SLIDERS: usize = 128;
+
+/// Details on why a beatmap was flagged as suspicious.
+pub struct SuspicionReport {
+    reason: &'static str,
+    value: f64,
+    threshold: f64,
+    objects: Vec<u32>,
+    start_time: Option<f64>,
+    end_time: Option<f64>,
+    message: String,
+}
+
+impl SuspicionReport {
+    pub fn new(map: &Beatmap, suspicion: &TooSuspicious) -> Self {
+        match suspicion {
+            TooSuspicious::ObjectCount => Self::object_count(map),
+            TooSuspicious::Length => Self::length(map),
+            TooSuspicious::Density => Self::density(map),
+            TooSuspicious::RedFlag => Self::red_flag(map),
+            TooSuspicious::SliderPositions => {
+                Self::sliders(map, "sliderPositions", "absurd positions", |h, repeats| {
+                    !too_many_repeats(repeats) && absurd_pos(h)
+                })
+            }
+            TooSuspicious::SliderRepeats => Self::sliders(
+                map,
+                "sliderRepeats",
+                "an absurd amount of repeats",
+                |h, repeats| too_many_repeats(repeats) && !absurd_pos(h),
+            ),
+            _ => Self {
+                reason: "unknown",
+                value: 0.0,
+                threshold: 0.0,
+                objects: Vec::new(),
+                start_time: None,
+                end_time: None,
+                message: suspicion.to_string(),
+            },
+        }
+    }
+
+    fn object_count(map: &Beatmap) -> Self {
+        let threshold = match map.mode {
+            GameMode::Taiko => MAX_OBJECTS_TAIKO,
+            GameMode::Osu | GameMode::Catch | GameMode::Mania => MAX_OBJECTS,
+        };
+
+        let n_objects = map.hit_objects.len();
+
+        Self {
+            reason: "objectCount",
+            value: n_objects as f64,
+            threshold: threshold as f64,
+            objects: Vec::new(),
+            start_time: None,
+            end_time: None,
+            message: format!("{n_objects} hitobjects exceed the maximum of {threshold}"),
+        }
+    }
+
+    fn length(map: &Beatmap) -> Self {
+        let objects = match map.hit_objects.len() {
+            0 => Vec::new(),
+            n => vec![0, n as u32 - 1],
+        };
+
+        let mut report = Self::with_objects(map, "length", objects);
+        let length = report.end_time.unwrap_or(0.0) - report.start_time.unwrap_or(0.0);
+
+        report.value = length;
+        report.threshold = MAX_LENGTH;
+        report.message = format!(
+            "{length}ms between the first and last hitobject exceed the maximum of {MAX_LENGTH}ms"
+        );
+
+        report
+    }
+
+    fn density(map: &Beatmap) -> Self {
+        let scale = match map.mode {
+            GameMode::Taiko => 2,
+            GameMode::Mania => cmp::max(1, map.cs as usize / 2),
+            GameMode::Osu | GameMode::Catch => 1,
+        };
+
+        let hit_objects = &map.hit_objects;
+
+        let dense = (0..hit_objects.len()).find_map(|i| {
+            MAX_DENSITY.into_iter().find_map(|(per_window, window)| {
+                let per_window = per_window * scale;
+                let start_time = hit_objects[i].start_time;
+
+                let too_dense = hit_objects
+                    .get(i + per_window)
+                    .is_some_and(|h| h.start_time - start_time < window);
+
+                if !too_dense {
+                    return None;
+                }
+
+                let end =
+                    i + hit_objects[i..].partition_point(|h| h.start_time - start_time < window);
+
+                Some((i..end, per_window, window))
+            })
+        });
+
+        let Some((range, per_window, window)) = dense else {
+            return Self::with_objects(map, "density", Vec::new());
+        };
+
+        let n_objects = range.len();
+        let objects = (range.start as u32..range.end as u32).collect();
+
+        let mut report = Self::with_objects(map, "density", objects);
+        report.value = n_objects as f64;
+        report.threshold = per_window as f64;
+        report.message =
+            format!("{n_objects} hitobjects within {window}ms exceed the maximum of {per_window}");
+
+        report
+    }
+
+    fn red_flag(map: &Beatmap) -> Self {
+        let objects: Vec<u32> =
+            suspicious_sliders(map, |h, repeats| too_many_repeats(repeats) && absurd_pos(h))
+                .take(1)
+                .collect();
+
+        let mut report = Self::with_objects(map, "redFlag", objects);
+        report.value = report.objects.len() as f64;
+        report.message = format!(
+            "A slider has more than {MAX_SLIDER_REPEATS} repeats and a position beyond \
+            {MAX_SLIDER_POS}"
+        );
+
+        report
+    }
+
+    fn sliders(
+        map: &Beatmap,
+        reason: &'static str,
+        description: &str,
+        f: impl Fn(&HitObject, usize) -> bool,
+    ) -> Self {
+        let objects: Vec<u32> = suspicious_sliders(map, f).collect();
+        let n_sliders = objects.len();
+
+        let mut report = Self::with_objects(map, reason, objects);
+        report.value = n_sliders as f64;
+        report.threshold = MAX_SUSPICIOUS_SLIDERS as f64;
+        report.message = format!(
+            "{n_sliders} sliders with {description} exceed the maximum of \
+            {MAX_SUSPICIOUS_SLIDERS}"
+        );
+
+        report
+    }
+
+    /// A report whose start and end time are those of the given hitobjects.
+    fn with_objects(map: &Beatmap, reason: &'static str, objects: Vec<u32>) -> Self {
+        let start_time = |idx: &u32| map.hit_objects[*idx as usize].start_time;
+
+        Self {
+            reason,
+            value: 0.0,
+            threshold: 0.0,
+            start_time: objects.first().map(start_time),
+            end_time: objects.last().map(start_time),
+            objects,
+            message: String::new(),
+        }
+    }
+}
+
+impl From<SuspicionReport> for JsSuspicionReport {
+    fn from(report: SuspicionReport) -> Self {
+        let obj = js_sys::Object::new();
+        let obj_as_ext = obj.unchecked_ref::<util::ObjectExt>();
+
+        let set = |key, value| obj_as_ext.set(util::static_str_to_js(key), value);
+
+        set("reason", util::static_str_to_js(report.reason).into());
+        set("value", report.value.into());
+        set("threshold", report.threshold.into());
+
+        if let Some(start_time) = report.start_time {
+            set("startTime", start_time.into());
+        }
+
+        if let Some(end_time) = report.end_time {
+            set("endTime", end_time.into());
+        }
+
+        set(
+            "objects",
+            js_sys::Uint32Array::from(report.objects.as_slice()).into(),
+        );
+        set("message", report.message.into());
+
+        JsValue::from(obj).unchecked_into()
+    }
+}
+
+/// Indices of the sliders that match the predicate.
+fn suspicious_sliders(
+    map: &Beatmap,
+    f: impl Fn(&HitObject, usize) -> bool,
+) -> impl Iterator<Item = u32> {
+    map.hit_objects
+        .iter()
+        .enumerate()
+        .filter_map(move |(i, h)| match h.kind {
+            HitObjectKind::Slider(ref slider) if f(h, slider.repeats) => Some(i as u32),
+            _ => None,
+        })
+}
+
+fn absurd_pos(h: &HitObject) -> bool {
+    h.pos.x.abs() > MAX_SLIDER_POS || h.pos.y.abs() > MAX_SLIDER_POS
+}
+
+const fn too_many_repeats(repeats: usize) -> bool {
+    repeats > MAX_SLIDER_REPEATS
+}
@@ -0,0 +1,467 @@
+use rosu_pp::{
+    Beatmap, Difficulty, GradualDifficulty,
+    any::DifficultyAttributes,
+    model::hit_object::{HitObjectKind, Slider},
+};
+use wasm_bindgen::{JsCast, JsValue, prelude::wasm_bindgen};
+use wasm_bindgen_futures::JsFuture;
+
+use crate::{
+    JsError, JsResult, args::difficulty::DifficultyArgs, beatmap::JsBeatmap, cache::PendingEntry,
+    hit_object, util,
+};
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = CalculationBudget)]
+    pub type JsCalculationBudget;
+
+    #[wasm_bindgen(js_name = setTimeout)]
+    fn set_timeout(handler: &js_sys::Function, timeout: i32);
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const _: &'static str = r#"/**
+* Limits for a calculation.
+*
+* If a limit is exceeded, the calculation throws a
+* `CalculationAbortedError`.
+*/
+export interface CalculationBudget {
+    /**
+    * The maximum amount of hitobjects of the beatmap.
+    */
+    maxObjects?: number;
+    /**
+    * The maximum amount of slider segments of all sliders combined.
+    *
+    * The segments of a slider are its control points as well as its
+    * estimated head, ticks, repeats, and tail. The estimation is based on
+    * the slider's length so that the limit is checked before any slider
+    * path is computed.
+    */
+    maxSliderSegments?: number;
+    /**
+    * Timestamp in ms, as returned by `Date.now()`, after which the
+    * calculation is aborted.
+    *
+    * The deadline is checked after every few hitobjects. Preparing the
+    * beatmap, e.g. computing slider paths, cannot be interrupted so
+    * `maxObjects` and `maxSliderSegments` should be specified as well.
+    */
+    deadline?: number;
+    /**
+    * Signal to cancel the calculation.
+    *
+    * Only asynchronous calculations can observe the signal while they are
+    * running; synchronous ones only check it before starting.
+    */
+    signal?: AbortSignal;
+}
+
+/**
+* The error that is thrown when a calculation exceeds its
+* `CalculationBudget` or is cancelled.
+*/
+export interface CalculationAbortedError extends Error {
+    name: "CalculationAbortedError";
+    /**
+    * The limit that was exceeded or `"cancelled"` if the signal was aborted.
+    */
+    reason: "maxObjects" | "maxSliderSegments" | "deadline" | "cancelled";
+}"#;
+
+/// The amount of hitobjects that are processed before the budget is checked
+/// again.
+const CHUNK_SIZE: usize = 256;
+
+/// The maximum length of a slider path, same as in rosu-map.
+const MAX_SLIDER_LEN: f64 = 100_000.0;
+
+#[derive(Default, serde::Deserialize)]
+#[serde(rename_all = "camelCase", rename = "Object")]
+struct BudgetArgs {
+    max_objects: Option<u32>,
+    max_slider_segments: Option<u32>,
+    deadline: Option<f64>,
+}
+
+pub struct Budget {
+    args: BudgetArgs,
+    signal: Option<JsValue>,
+}
+
+impl Budget {
+    /// @throws Throws an error if the budget is invalid
+    pub fn from_value(budget: Option<&JsCalculationBudget>) -> JsResult<Self> {
+        let Some(budget) = budget else {
+            return Ok(Self {
+                args: BudgetArgs::default(),
+                signal: None,
+            });
+        };
+
+        let args = util::from_value::<BudgetArgs>(budget)?;
+
+        let signal = js_sys::Reflect::get(budget, &util::static_str_to_js("signal"))?;
+        let signal = (!signal.is_undefined() && !signal.is_null()).then_some(signal);
+
+        Ok(Self { args, signal })
+    }
+
+    fn check_map(&self, map: &Beatmap) -> Result<(), Aborted> {
+        if let Some(max_objects) = self.args.max_objects
+            && map.hit_objects.len() > max_objects as usize
+        {
+            return Err(Aborted::MaxObjects);
+        }
+
+        if let Some(max_segments) = self.args.max_slider_segments {
+            let segments: f64 = map
+                .hit_objects
+                .iter()
+                .map(|h| match h.kind {
+                    HitObjectKind::Slider(ref slider) => slider_segments(map, h.start_time, slider),
+                    HitObjectKind::Circle | HitObjectKind::Spinner(_) | HitObjectKind::Hold(_) => {
+                        0.0
+                    }
+                })
+                .sum();
+
+            if segments > f64::from(max_segments) {
+                return Err(Aborted::MaxSliderSegments);
+            }
+        }
+
+        self.check_progress()
+    }
+
+    fn check_progress(&self) -> Result<(), Aborted> {
+        if let Some(ref signal) = self.signal {
+            let aborted = js_sys::Reflect::get(signal, &util::static_str_to_js("aborted"))
+                .is_ok_and(|aborted| aborted.is_truthy());
+
+            if aborted {
+                return Err(Aborted::Cancelled);
+            }
+        }
+
+        if let Some(deadline) = self.args.deadline
+            && js_sys::Date::now() > deadline
+        {
+            return Err(Aborted::Deadline);
+        }
+
+        Ok(())
+    }
+}
+
+/// Estimate the amount of control points and nested objects of a slider
+/// without computing its path.
+///
+/// The path's length is its expected distance or, if there is none, the
+/// distance along its control points which bounds the length of most curves.
+/// Nested objects are the head as well as the ticks and the end of each span,
+/// i.e. a repeat or the tail, same as rosu-map generates them.
+fn slider_segments(map: &Beatmap, start_time: f64, slider: &Slider) -> f64 {
+    let len = slider
+        .expected_dist
+        .unwrap_or_else(|| {
+            slider
+                .control_points
+                .windows(2)
+                .map(|window| f64::from(window[0].pos.distance(window[1].pos)))
+                .sum()
+        })
+        .clamp(0.0, MAX_SLIDER_LEN);
+
+    let beat_len = hit_object::point_at(&map.timing_points, start_time, |point| point.time)
+        .or_else(|| map.timing_points.first())
+        .map_or(1000.0, |point| point.beat_len);
+
+    let (slider_velocity, generate_ticks) =
+        hit_object::point_at(&map.difficulty_points, start_time, |point| point.time)
+            .map_or((1.0, true), |point| {
+                (point.slider_velocity, point.generate_ticks)
+            });
+
+    let scoring_dist = hit_object::slider_velocity(map, start_time) * beat_len;
+
+    let tick_dist_multiplier = if map.version < 8 {
+        slider_velocity.recip()
+    } else {
+        1.0
+    };
+
+    let tick_dist = scoring_dist / map.slider_tick_rate * tick_dist_multiplier;
+
+    // Same as rosu-map, ticks require a positive distance and are only
+    // generated before the end of the span
+    let ticks_per_span = if generate_ticks && len > 0.0 && tick_dist > 0.0 {
+        ((len / tick_dist).ceil() - 1.0).max(0.0)
+    } else {
+        0.0
+    };
+
+    let span_count = slider.span_count() as f64;
+
+    slider.control_points.len() as f64 + 1.0 + span_count * (ticks_per_span + 1.0)
+}
+
+/// The reason why a calculation was aborted.
+#[derive(Copy, Clone)]
+pub enum Aborted {
+    MaxObjects,
+    MaxSliderSegments,
+    Deadline,
+    Cancelled,
+}
+
+impl Aborted {
+    const fn reason(self) -> &'static str {
+        match self {
+            Self::MaxObjects => "maxObjects",
+            Self::MaxSliderSegments => "maxSliderSegments",
+            Self::Deadline => "deadline",
+            Self::Cancelled => "cancelled",
+        }
+    }
+
+    const fn message(self) -> &'static str {
+        match self {
+            Self::MaxObjects => "The beatmap has too many hitobjects",
+            Self::MaxSliderSegments => "The beatmap's slider paths have too many segments",
+            Self::Deadline => "The calculation exceeded its deadline",
+            Self::Cancelled => "The calculation was cancelled",
+        }
+    }
+}
+
+impl From<Aborted> for JsError {
+    fn from(aborted: Aborted) -> Self {
+        let err = js_sys::Error::new(aborted.message());
+        err.set_name("CalculationAbortedError");
+
+        err.unchecked_ref::<util::ObjectExt>().set(
+            util::static_str_to_js("reason"),
+            util::static_str_to_js(aborted.reason()).into(),
+        );
+
+        JsError::from(JsValue::from(err))
+    }
+}
+
+/// Difficulty calculation that checks its budget after every few hitobjects.
+///
+/// Processing hitobjects in chunks through a gradual calculator results in
+/// the same attributes as a regular calculation.
+pub struct BudgetedDifficulty {
+    gradual: GradualDifficulty,
+    /// The amount of hitobjects that still need to be processed.
+    remaining: usize,
+    budget: Budget,
+    /// Only set if there are no hitobjects to process.
+    attrs: Option<DifficultyAttributes>,
+}
+
+impl BudgetedDifficulty {
+    pub fn new(
+        difficulty: Difficulty,
+        passed_objects: Option<u32>,
+        map: &Beatmap,
+        budget: Budget,
+    ) -> Result<Self, Aborted> {
+        budget.check_map(map)?;
+
+        let gradual = GradualDifficulty::new(difficulty.clone(), map);
+
+        let remaining = match passed_objects {
+            Some(passed_objects) => gradual.len().min(passed_objects as usize),
+            None => gradual.len(),
+        };
+
+        // Gradual calculators only provide attributes after a hitobject
+        let attrs = (remaining == 0).then(|| difficulty.calculate(map));
+
+        Ok(Self {
+            gradual,
+            remaining,
+            budget,
+            attrs,
+        })
+    }
+
+    /// Process all hitobjects at once.
+    pub fn calculate(mut self) -> Result<DifficultyAttributes, Aborted> {
+        loop {
+            if let Some(attrs) = self.step()? {
+                return Ok(attrs);
+            }
+        }
+    }
+
+    /// Process the hitobjects in chunks and yield to the event loop
+    /// inbetween so that the budget's signal can be observed.
+    pub async fn calculate_async(mut self) -> Result<DifficultyAttributes, Aborted> {
+        loop {
+            if let Some(attrs) = self.step()? {
+                return Ok(attrs);
+            }
+
+            yield_now().await;
+        }
+    }
+
+    /// Process the next chunk of hitobjects and return the attributes once
+    /// all hitobjects are processed.
+    fn step(&mut self) -> Result<Option<DifficultyAttributes>, Aborted> {
+        if let Some(attrs) = self.attrs.take() {
+            return Ok(Some(attrs));
+        }
+
+        self.budget.check_progress()?;
+
+        let n = self.remaining.min(CHUNK_SIZE);
+        self.remaining -= n;
+
+        let attrs = self.gradual.nth(n - 1);
+
+        if self.remaining > 0 {
+            return Ok(None);
+        }
+
+        match attrs {
+            Some(attrs) => Ok(Some(attrs)),
+            // `remaining` never exceeds the gradual calculator's length
+            None => unreachable!(),
+        }
+    }
+}
+
+/// Calculate difficulty attributes within the budget, consulting the cache
+/// if there is one.
+pub fn calculate(
+    args: &DifficultyArgs,
+    map: &JsBeatmap,
+    budget: Budget,
+) -> JsResult<DifficultyAttributes> {
+    let (difficulty, pending) = match Prepared::new(args, map, budget) {
+        Prepared::Cached(attrs) => return Ok(attrs),
+        Prepared::Calculate {
+            difficulty,
+            pending,
+        } => (difficulty, pending),
+    };
+
+    let attrs = difficulty?.calculate()?;
+
+    if let Some(pending) = pending {
+        pending.insert(attrs.clone());
+    }
+
+    Ok(attrs)
+}
+
+/// Same as [`calculate`] but yields to the event loop after every few
+/// hitobjects.
+pub fn calculate_async(
+    args: &DifficultyArgs,
+    map: &JsBeatmap,
+    budget: Budget,
+) -> impl Future<Output = JsResult<DifficultyAttributes>> + 'static {
+    // Preparing has to happen beforehand since the beatmap is borrowed
+    let prepared = Prepared::new(args, map, budget);
+
+    async move {
+        let (difficulty, pending) = match prepared {
+            Prepared::Cached(attrs) => return Ok(attrs),
+            Prepared::Calculate {
+                difficulty,
+                pending,
+            } => (difficulty, pending),
+        };
+
+        let attrs = difficulty?.calculate_async().await?;
+
+        if let Some(pending) = pending {
+            pending.insert(attrs.clone());
+        }
+
+        Ok(attrs)
+    }
+}
+
+/// Either cached attributes or a calculation that is ready to start.
+enum Prepared {
+    Cached(DifficultyAttributes),
+    Calculate {
+        difficulty: Result<Box<BudgetedDifficulty>, Aborted>,
+        pending: Option<PendingEntry>,
+    },
+}
+
+impl Prepared {
+    fn new(args: &DifficultyArgs, map: &JsBeatmap, budget: Budget) -> Self {
+        let pending = match args.cache {
+            Some(ref cache) => match cache.get(args, map) {
+                Some(attrs) => return Self::Cached(attrs),
+                None => Some(cache.pending(args, map)),
+            },
+            None => None,
+        };
+
+        let difficulty = BudgetedDifficulty::new(
+            args.to_difficulty(),
+            args.passed_objects,
+            &map.inner,
+            budget,
+        )
+        .map(Box::new);
+
+        Self::Calculate {
+            difficulty,
+            pending,
+        }
+    }
+}
+
+/// Let the event loop process other tasks, e.g. an abort signal.
+async fn yield_now() {
+    let promise = js_sys::Promise::new(&mut |resolve, _| set_timeout(&resolve, 0));
+
+    // The promise never rejects
+    let _ = JsFuture::from(promise).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segments(slider_line: &str) -> f64 {
+        let content = format!(
+            "osu file format v14\n\n[Difficulty]\nSliderMultiplier:1\nSliderTickRate:1\n\n\
+            [TimingPoints]\n0,500,4,2,0,100,1,0\n\n[HitObjects]\n{slider_line}\n"
+        );
+
+        let map = Beatmap::from_bytes(content.as_bytes()).unwrap();
+        let h = &map.hit_objects[0];
+
+        let HitObjectKind::Slider(ref slider) = h.kind else {
+            panic!("expected a slider");
+        };
+
+        slider_segments(&map, h.start_time, slider)
+    }
+
+    #[test]
+    fn slider_segments_include_nested_objects() {
+        // Two control points, a head, one tick, and a tail
+        assert_eq!(segments("0,0,1000,2,0,L|200:0,1,200"), 5.0);
+
+        // Each repeat adds a span with its own tick
+        assert_eq!(segments("0,0,1000,2,0,L|200:0,5,200"), 13.0);
+
+        // The length bounds the amount of ticks
+        assert_eq!(segments("0,0,1000,2,0,L|200:0,1,10000"), 103.0);
+    }
+}
@@ -0,0 +1,330 @@
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    fmt::{Formatter, Result as FmtResult},
+    rc::Rc,
+};
+
+use rosu_mods::GameModIntermode;
+use rosu_pp::any::DifficultyAttributes;
+use serde::de;
+use wasm_bindgen::{convert::RefFromWasmAbi, prelude::wasm_bindgen};
+
+use crate::{
+    JsResult, args::difficulty::DifficultyArgs, beatmap::JsBeatmap, deserializer::JsDeserializer,
+    util::FieldVisitor,
+};
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = DifficultyCache)]
+    pub type JsDifficultyCacheRef;
+}
+
+/// Cache for difficulty attributes.
+///
+/// Attributes are stored for the content of a beatmap alongside the
+/// difficulty settings like mods or clock rate that they were calculated
+/// with. Once the capacity is reached, the least recently used attributes are
+/// discarded.
+///
+/// To use the cache, pass it via the `cache` argument to `Difficulty` or
+/// `Performance`.
+#[wasm_bindgen(js_name = DifficultyCache)]
+pub struct JsDifficultyCache {
+    inner: DifficultyCache,
+}
+
+#[wasm_bindgen(js_class = DifficultyCache)]
+impl JsDifficultyCache {
+    /// Create a new cache that stores attributes of up to `capacity`
+    /// calculations.
+    ///
+    /// Defaults to a capacity of 128.
+    #[wasm_bindgen(constructor)]
+    pub fn new(capacity: Option<usize>) -> JsDifficultyCache {
+        let capacity = capacity.unwrap_or(DifficultyCache::DEFAULT_CAPACITY);
+
+        Self {
+            inner: DifficultyCache::new(capacity),
+        }
+    }
+
+    /// Remove all stored attributes.
+    pub fn clear(&self) {
+        self.inner.0.borrow_mut().entries.clear();
+    }
+
+    /// The amount of stored attributes.
+    #[wasm_bindgen(getter)]
+    pub fn size(&self) -> usize {
+        self.inner.0.borrow().entries.len()
+    }
+
+    /// The maximum amount of stored attributes.
+    #[wasm_bindgen(getter)]
+    pub fn capacity(&self) -> usize {
+        self.inner.0.borrow().capacity
+    }
+}
+
+impl JsDifficultyCache {
+    pub fn deserialize<'de, D: de::Deserializer<'de>>(
+        d: D,
+    ) -> Result<Option<DifficultyCache>, D::Error> {
+        struct CacheField;
+
+        impl CacheField {
+            const NAME: &'static str = "__wbg_ptr";
+        }
+
+        impl<'de> de::Deserialize<'de> for CacheField {
+            fn deserialize<D: de::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+                d.deserialize_str(FieldVisitor::new(Self::NAME))
+                    .map(|_| Self)
+            }
+        }
+
+        struct CacheVisitor;
+
+        impl<'de> de::Visitor<'de> for CacheVisitor {
+            type Value = Option<DifficultyCache>;
+
+            fn expecting(&self, f: &mut Formatter) -> FmtResult {
+                f.write_str("a DifficultyCache")
+            }
+
+            fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+                Ok(None)
+            }
+
+            fn visit_some<D: de::Deserializer<'de>>(self, d: D) -> Result<Self::Value, D::Error> {
+                d.deserialize_struct("DifficultyCache", &[CacheField::NAME], self)
+            }
+
+            fn visit_map<A: de::MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                if map.next_key::<CacheField>()?.is_none() {
+                    return Err(de::Error::custom("expected a DifficultyCache"));
+                }
+
+                let ptr_u32 = map.next_value::<u32>()?;
+                let instance_ref = unsafe { JsDifficultyCache::ref_from_abi(ptr_u32) };
+
+                // Only the shared cache is kept so that the instance itself
+                // is not borrowed beyond this point.
+                Ok(Some(instance_ref.inner.clone()))
+            }
+        }
+
+        d.deserialize_option(CacheVisitor)
+    }
+
+    pub fn from_ref(cache: Option<JsDifficultyCacheRef>) -> JsResult<Option<DifficultyCache>> {
+        match cache {
+            Some(ref cache) => Self::deserialize(JsDeserializer::from_ref(cache)),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Shared handle to the storage of a [`JsDifficultyCache`].
+#[derive(Clone)]
+pub struct DifficultyCache(Rc<RefCell<Lru>>);
+
+impl DifficultyCache {
+    const DEFAULT_CAPACITY: usize = 128;
+
+    fn new(capacity: usize) -> Self {
+        let lru = Lru {
+            entries: HashMap::new(),
+            capacity,
+            tick: 0,
+        };
+
+        Self(Rc::new(RefCell::new(lru)))
+    }
+
+    /// Returns the stored attributes for the given beatmap and settings or
+    /// calculates and stores them if there are none.
+    pub fn get_or_calculate(&self, args: &DifficultyArgs, map: &JsBeatmap) -> DifficultyAttributes {
+        if let Some(attrs) = self.get(args, map) {
+            return attrs;
+        }
+
+        let attrs = args.to_difficulty().calculate(&map.inner);
+        self.insert(args, map, attrs.clone());
+
+        attrs
+    }
+
+    /// Returns the stored attributes for the given beatmap and settings.
+    pub fn get(&self, args: &DifficultyArgs, map: &JsBeatmap) -> Option<DifficultyAttributes> {
+        self.0.borrow_mut().get(&CacheKey::new(args, map))
+    }
+
+    /// Store the attributes for the given beatmap and settings.
+    pub fn insert(&self, args: &DifficultyArgs, map: &JsBeatmap, attrs: DifficultyAttributes) {
+        self.0.borrow_mut().insert(CacheKey::new(args, map), attrs);
+    }
+
+    /// Prepare storing attributes for the given beatmap and settings once
+    /// they are calculated, without borrowing the beatmap.
+    pub fn pending(&self, args: &DifficultyArgs, map: &JsBeatmap) -> PendingEntry {
+        PendingEntry {
+            cache: self.clone(),
+            key: CacheKey::new(args, map),
+        }
+    }
+}
+
+/// Attributes that are yet to be stored in a [`DifficultyCache`].
+pub struct PendingEntry {
+    cache: DifficultyCache,
+    key: CacheKey,
+}
+
+impl PendingEntry {
+    pub fn insert(self, attrs: DifficultyAttributes) {
+        self.cache.0.borrow_mut().insert(self.key, attrs);
+    }
+}
+
+impl PartialEq for DifficultyCache {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+struct Lru {
+    entries: HashMap<CacheKey, CacheEntry>,
+    capacity: usize,
+    tick: u64,
+}
+
+struct CacheEntry {
+    attrs: DifficultyAttributes,
+    last_used: u64,
+}
+
+impl Lru {
+    fn get(&mut self, key: &CacheKey) -> Option<DifficultyAttributes> {
+        self.tick += 1;
+        let entry = self.entries.get_mut(key)?;
+        entry.last_used = self.tick;
+
+        Some(entry.attrs.clone())
+    }
+
+    fn insert(&mut self, key: CacheKey, attrs: DifficultyAttributes) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        if self.entries.len() >= self.capacity {
+            let lru_key = self
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(key, _)| key.clone());
+
+            if let Some(lru_key) = lru_key {
+                self.entries.remove(&lru_key);
+            }
+        }
+
+        self.tick += 1;
+
+        let entry = CacheEntry {
+            attrs,
+            last_used: self.tick,
+        };
+
+        self.entries.insert(key, entry);
+    }
+}
+
+/// Identifies a calculation through the digest of the beatmap's content and
+/// the normalized difficulty settings.
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct CacheKey {
+    map_hash: [u8; 32],
+    settings: SettingsKey,
+}
+
+impl CacheKey {
+    fn new(args: &DifficultyArgs, map: &JsBeatmap) -> Self {
+        let n_objects = map.inner.hit_objects.len();
+
+        let mut settings = SettingsKey::new(args);
+
+        settings.passed_objects = settings
+            .passed_objects
+            .filter(|&passed_objects| (passed_objects as usize) < n_objects);
+
+        Self {
+            map_hash: map.hash,
+            settings,
+        }
+    }
+}
+
+/// The normalized difficulty settings of [`DifficultyArgs`].
+///
+/// Settings that result in the same calculation map to the same key, e.g.
+/// an unset clock rate and the clock rate of the mods.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct SettingsKey {
+    mods: String,
+    clock_rate: u64,
+    ar: Option<(u32, bool)>,
+    cs: Option<(u32, bool)>,
+    hp: Option<(u32, bool)>,
+    od: Option<(u32, bool)>,
+    passed_objects: Option<u32>,
+    hardrock_offsets: bool,
+    lazer: bool,
+}
+
+impl SettingsKey {
+    pub fn new(args: &DifficultyArgs) -> Self {
+        let custom_attr =
+            |value: Option<f32>, with_mods: bool| value.map(|value| (value.to_bits(), with_mods));
+
+        Self {
+            mods: serde_json::to_string(&args.mods).unwrap_or_default(),
+            clock_rate: args.clock_rate().to_bits(),
+            ar: custom_attr(args.ar, args.ar_with_mods),
+            cs: custom_attr(args.cs, args.cs_with_mods),
+            hp: custom_attr(args.hp, args.hp_with_mods),
+            od: custom_attr(args.od, args.od_with_mods),
+            passed_objects: args.passed_objects,
+            hardrock_offsets: args
+                .hardrock_offsets
+                .unwrap_or_else(|| args.mods.contains_intermode(GameModIntermode::HardRock)),
+            lazer: args.lazer.unwrap_or(true),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalized_clock_rate() {
+        let unset = DifficultyArgs::default();
+
+        let explicit = DifficultyArgs {
+            clock_rate: Some(1.0),
+            ..Default::default()
+        };
+
+        let different = DifficultyArgs {
+            clock_rate: Some(1.5),
+            ..Default::default()
+        };
+
+        assert!(SettingsKey::new(&unset) == SettingsKey::new(&explicit));
+        assert!(SettingsKey::new(&unset) != SettingsKey::new(&different));
+    }
+}
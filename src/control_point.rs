@@ -0,0 +1,164 @@
+use rosu_map::section::{hit_objects::hit_samples::SampleBank, timing_points::SamplePoint};
+use rosu_pp::model::control_point::{DifficultyPoint, EffectPoint, TimingPoint};
+use wasm_bindgen::prelude::*;
+
+use crate::util;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = TimingPoint)]
+    pub type JsTimingPoint;
+
+    #[wasm_bindgen(typescript_type = DifficultyPoint)]
+    pub type JsDifficultyPoint;
+
+    #[wasm_bindgen(typescript_type = EffectPoint)]
+    pub type JsEffectPoint;
+
+    #[wasm_bindgen(typescript_type = SamplePoint)]
+    pub type JsSamplePoint;
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const _: &'static str = r#"/**
+* Timing-related info of a control point i.e. an uninherited timing point.
+*/
+export interface TimingPoint {
+    /**
+    * The start time in milliseconds.
+    */
+    time: number;
+    /**
+    * The duration of a beat in milliseconds.
+    */
+    beatLen: number;
+    /**
+    * The beats per minute.
+    */
+    bpm: number;
+}
+
+/**
+* Difficulty-related info of a control point.
+*/
+export interface DifficultyPoint {
+    /**
+    * The start time in milliseconds.
+    */
+    time: number;
+    /**
+    * The slider velocity multiplier.
+    */
+    sliderVelocity: number;
+    /**
+    * The beat length multiplier of slider ticks.
+    */
+    bpmMultiplier: number;
+    /**
+    * Whether slider ticks should be generated.
+    */
+    generateTicks: boolean;
+}
+
+/**
+* Effect-related info of a control point.
+*/
+export interface EffectPoint {
+    /**
+    * The start time in milliseconds.
+    */
+    time: number;
+    /**
+    * Whether kiai time is active.
+    */
+    kiai: boolean;
+    /**
+    * The scroll speed multiplier.
+    */
+    scrollSpeed: number;
+}
+
+/**
+* Sample-related info of a control point.
+*/
+export interface SamplePoint {
+    /**
+    * The start time in milliseconds.
+    */
+    time: number;
+    /**
+    * The sample bank i.e. `0` for none, `1` for normal, `2` for soft, and `3`
+    * for drum.
+    */
+    sampleBank: number;
+    /**
+    * The sample volume in percent.
+    */
+    sampleVolume: number;
+    /**
+    * The index of the custom sample bank; `0` for the default samples.
+    */
+    customSampleBank: number;
+}"#;
+
+fn new_object(f: impl FnOnce(&dyn Fn(&'static str, JsValue))) -> JsValue {
+    let obj = js_sys::Object::new();
+    let obj_as_ext = obj.unchecked_ref::<util::ObjectExt>();
+
+    f(&|key, value| obj_as_ext.set(util::static_str_to_js(key), value));
+
+    obj.into()
+}
+
+impl From<&TimingPoint> for JsTimingPoint {
+    fn from(point: &TimingPoint) -> Self {
+        new_object(|set| {
+            set("time", point.time.into());
+            set("beatLen", point.beat_len.into());
+            set("bpm", point.bpm().into());
+        })
+        .into()
+    }
+}
+
+impl From<&DifficultyPoint> for JsDifficultyPoint {
+    fn from(point: &DifficultyPoint) -> Self {
+        new_object(|set| {
+            set("time", point.time.into());
+            set("sliderVelocity", point.slider_velocity.into());
+            set("bpmMultiplier", point.bpm_multiplier.into());
+            set("generateTicks", point.generate_ticks.into());
+        })
+        .into()
+    }
+}
+
+impl From<&EffectPoint> for JsEffectPoint {
+    fn from(point: &EffectPoint) -> Self {
+        new_object(|set| {
+            set("time", point.time.into());
+            set("kiai", point.kiai.into());
+            set("scrollSpeed", point.scroll_speed.into());
+        })
+        .into()
+    }
+}
+
+impl From<&SamplePoint> for JsSamplePoint {
+    fn from(point: &SamplePoint) -> Self {
+        let sample_bank: u8 = match point.sample_bank {
+            SampleBank::None => 0,
+            SampleBank::Normal => 1,
+            SampleBank::Soft => 2,
+            SampleBank::Drum => 3,
+        };
+
+        new_object(|set| {
+            set("time", point.time.into());
+            set("sampleBank", sample_bank.into());
+            set("sampleVolume", point.sample_volume.into());
+            set("customSampleBank", point.custom_sample_bank.into());
+        })
+        .into()
+    }
+}
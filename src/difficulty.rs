@@ -1,13 +1,20 @@
-use wasm_bindgen::prelude::wasm_bindgen;
+use rosu_pp::model::mode::GameMode;
+use wasm_bindgen::{JsValue, prelude::wasm_bindgen};
 
 use crate::{
-    args::difficulty::{DifficultyArgs, JsDifficultyArgs},
+    analysis::{JsStrainAnalysis, StrainAnalysis},
+    args::difficulty::{
+        DifficultyArgs, JsDifficultyArgs, JsDifficultyArgsList, JsStrainAnalysisArgs,
+        JsStrainsArgs, StrainAnalysisArgs, StrainsArgs,
+    },
     attributes::difficulty::JsDifficultyAttributes,
-    beatmap::JsBeatmap,
+    beatmap::{JsBeatmap, JsBeatmapList},
+    budget::{self, Budget, JsCalculationBudget},
+    cache::{JsDifficultyCache, JsDifficultyCacheRef},
     deserializer::JsDeserializer,
-    gradual::{difficulty::JsGradualDifficulty, performance::JsGradualPerformance},
+    gradual::{self, difficulty::JsGradualDifficulty, performance::JsGradualPerformance},
     mods::JsGameMods,
-    strains::JsStrains,
+    strains::{JsStrains, SectionLayout},
     util, JsResult,
 };
 
@@ -33,16 +40,202 @@ impl JsDifficulty {
     }
 
     /// Perform the difficulty calculation.
+    ///
+    /// If a `DifficultyCache` was specified, previously calculated attributes
+    /// for the same beatmap and settings are reused.
     pub fn calculate(&self, map: &JsBeatmap) -> JsDifficultyAttributes {
-        JsDifficultyAttributes::from(self.args.to_difficulty().calculate(&map.inner))
+        JsDifficultyAttributes::from(self.args.calculate(map))
+    }
+
+    /// Perform the difficulty calculation within the given budget.
+    ///
+    /// Hitobjects are processed in chunks and the budget is checked
+    /// inbetween. The resulting attributes are the same as for `calculate`.
+    ///
+    /// If a `DifficultyCache` was specified, previously calculated attributes
+    /// for the same beatmap and settings are reused without checking the
+    /// budget.
+    /// @throws Throws a `CalculationAbortedError` if the budget is exceeded or an error if the budget is invalid
+    #[wasm_bindgen(js_name = calculateWithBudget)]
+    pub fn calculate_with_budget(
+        &self,
+        map: &JsBeatmap,
+        budget: &JsCalculationBudget,
+    ) -> JsResult<JsDifficultyAttributes> {
+        let budget = Budget::from_value(Some(budget))?;
+
+        budget::calculate(&self.args, map, budget).map(From::from)
+    }
+
+    /// Same as `calculateWithBudget` but the calculation yields to the event
+    /// loop after every few hitobjects so that it does not block other tasks
+    /// and can be cancelled through the budget's `signal`.
+    ///
+    /// Preparing the beatmap, e.g. computing slider paths, happens before the
+    /// first yield.
+    ///
+    /// The promise rejects with a `CalculationAbortedError` if the budget is
+    /// exceeded.
+    /// @throws Throws an error if the budget is invalid
+    #[wasm_bindgen(
+        js_name = calculateAsync,
+        unchecked_return_type = "Promise<DifficultyAttributes>"
+    )]
+    pub fn calculate_async(
+        &self,
+        map: &JsBeatmap,
+        budget: Option<JsCalculationBudget>,
+    ) -> JsResult<js_sys::Promise> {
+        let budget = Budget::from_value(budget.as_ref())?;
+        let calculation = budget::calculate_async(&self.args, map, budget);
+
+        let promise = wasm_bindgen_futures::future_to_promise(async move {
+            calculation
+                .await
+                .map(|attrs| JsDifficultyAttributes::from(attrs).into())
+                .map_err(JsValue::from)
+        });
+
+        Ok(promise)
+    }
+
+    /// Perform the difficulty calculation for multiple sets of arguments on
+    /// the same beatmap.
+    ///
+    /// Returns the attributes in the same order as the given arguments.
+    /// Arguments with the same settings, e.g. an unset clock rate and the
+    /// clock rate of the mods, are only calculated once. Beyond that,
+    /// calculations do not share any work.
+    ///
+    /// If the `threads` feature is enabled and a thread pool was initialized
+    /// through `initThreadPool`, the calculations are performed in parallel.
+    /// @throws Throws an error if the arguments are invalid
+    #[wasm_bindgen(js_name = calculateMany)]
+    pub fn calculate_many(
+        map: &JsBeatmap,
+        args: &JsDifficultyArgsList,
+    ) -> JsResult<Vec<JsDifficultyAttributes>> {
+        let args_list = util::from_value::<Vec<DifficultyArgs>>(args)?;
+
+        let attrs = DifficultyArgs::calculate_many(&args_list, map)
+            .into_iter()
+            .map(JsDifficultyAttributes::from)
+            .collect();
+
+        Ok(attrs)
+    }
+
+    /// Perform the difficulty calculation for multiple sets of arguments on
+    /// each of the given beatmaps.
+    ///
+    /// Returns a list for each beatmap in the same order as the beatmaps,
+    /// each containing the attributes in the same order as the given
+    /// arguments. If no arguments are given, the default settings are used.
+    /// Identical beatmaps and arguments with the same settings are only
+    /// calculated once.
+    ///
+    /// If the `threads` feature is enabled and a thread pool was initialized
+    /// through `initThreadPool`, the calculations of all beatmaps are
+    /// performed in parallel.
+    /// @throws Throws an error if the beatmaps or arguments are invalid
+    #[wasm_bindgen(
+        js_name = calculateBatch,
+        unchecked_return_type = "DifficultyAttributes[][]"
+    )]
+    pub fn calculate_batch(
+        maps: &JsBeatmapList,
+        args: Option<JsDifficultyArgsList>,
+    ) -> JsResult<js_sys::Array> {
+        let maps = JsBeatmap::from_list(maps)?;
+        let maps: Vec<&JsBeatmap> = maps.iter().map(|map| &**map).collect();
+
+        let args_list = match args {
+            Some(ref args) => util::from_value::<Vec<DifficultyArgs>>(args)?,
+            None => vec![DifficultyArgs::default()],
+        };
+
+        let mut attrs = DifficultyArgs::calculate_batch(&args_list, &maps).into_iter();
+
+        let batch = maps
+            .iter()
+            .map(|_| {
+                attrs
+                    .by_ref()
+                    .take(args_list.len())
+                    .map(|attrs| JsValue::from(JsDifficultyAttributes::from(attrs)))
+                    .collect::<js_sys::Array>()
+            })
+            .collect();
+
+        Ok(batch)
     }
 
     /// Perform the difficulty calculation but instead of evaluating strain
     /// values, return them as is.
     ///
     /// Suitable to plot the difficulty over time.
-    pub fn strains(&self, map: &JsBeatmap) -> JsStrains {
-        self.args.to_difficulty().strains(&map.inner).into()
+    ///
+    /// If `perObject` is specified, the strains of each hitobject are
+    /// included alongside their start time. If `sectionTimes` is specified,
+    /// the start time of each strain section is included.
+    /// @throws Throws an error if the options are invalid
+    pub fn strains(&self, map: &JsBeatmap, options: Option<JsStrainsArgs>) -> JsResult<JsStrains> {
+        let options = options
+            .as_deref()
+            .map(util::from_value::<StrainsArgs>)
+            .transpose()?
+            .unwrap_or_default();
+
+        let mut strains = JsStrains::from(self.args.to_difficulty().strains(&map.inner));
+
+        if !options.per_object && !options.section_times {
+            return Ok(strains);
+        }
+
+        let start_times = self.object_start_times(map);
+        let layout = self.section_layout(map, &start_times, strains.section_len);
+
+        if options.per_object {
+            strains = strains.with_objects(&start_times, &layout);
+        }
+
+        if options.section_times {
+            strains = strains.with_section_times(&layout);
+        }
+
+        Ok(strains)
+    }
+
+    /// Find the hardest sections of each skill as well as how concentrated
+    /// the difficulty is.
+    ///
+    /// Sections are formed by consecutive strain sections so the same
+    /// caveats as for `strains` apply.
+    /// @throws Throws an error if the options are invalid
+    #[wasm_bindgen(js_name = analyzeStrains)]
+    pub fn analyze_strains(
+        &self,
+        map: &JsBeatmap,
+        options: Option<JsStrainAnalysisArgs>,
+    ) -> JsResult<JsStrainAnalysis> {
+        let options = options
+            .as_deref()
+            .map(util::from_value::<StrainAnalysisArgs>)
+            .transpose()?
+            .unwrap_or_default();
+
+        let strains = JsStrains::from(self.args.to_difficulty().strains(&map.inner));
+        let start_times = self.object_start_times(map);
+        let layout = self.section_layout(map, &start_times, strains.section_len);
+        let analysis = StrainAnalysis::new(
+            &strains,
+            &start_times,
+            layout,
+            self.args.clock_rate(),
+            &options,
+        );
+
+        Ok(analysis.into())
     }
 
     /// Returns a gradual difficulty calculator for the current difficulty settings.
@@ -128,4 +321,46 @@ impl JsDifficulty {
     pub fn set_hardrock_offsets(&mut self, hardrock_offsets: Option<bool>) {
         self.args.hardrock_offsets = hardrock_offsets;
     }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_cache(&mut self, cache: Option<JsDifficultyCacheRef>) -> JsResult<()> {
+        self.args.cache = JsDifficultyCache::from_ref(cache)?;
+
+        Ok(())
+    }
+}
+
+impl JsDifficulty {
+    /// The start times of the hitobjects that are considered for the
+    /// calculation.
+    fn object_start_times(&self, map: &JsBeatmap) -> Vec<f64> {
+        let mut start_times = gradual::object_start_times(&map.inner);
+
+        if let Some(passed_objects) = self.args.passed_objects {
+            start_times.truncate(passed_objects as usize);
+        }
+
+        start_times
+    }
+
+    /// The time span of strain sections.
+    ///
+    /// In osu!taiko, strains are based on all hitobjects, not only on the
+    /// circles of the given start times.
+    fn section_layout(
+        &self,
+        map: &JsBeatmap,
+        object_start_times: &[f64],
+        section_len: f64,
+    ) -> SectionLayout {
+        let clock_rate = self.args.clock_rate();
+
+        if map.inner.mode != GameMode::Taiko {
+            return SectionLayout::new(object_start_times, clock_rate, section_len);
+        }
+
+        let start_times: Vec<_> = map.inner.hit_objects.iter().map(|h| h.start_time).collect();
+
+        SectionLayout::new(&start_times, clock_rate, section_len)
+    }
 }
@@ -1,14 +1,94 @@
-use rosu_pp::GradualDifficulty;
-use wasm_bindgen::prelude::wasm_bindgen;
+use rosu_pp::any::DifficultyAttributes;
+use wasm_bindgen::{JsCast, JsValue, prelude::wasm_bindgen};
 
 use crate::{
-    attributes::difficulty::JsDifficultyAttributes, beatmap::JsBeatmap, difficulty::JsDifficulty,
+    JsError, JsResult, attributes::difficulty::JsDifficultyAttributes, beatmap::JsBeatmap,
+    difficulty::JsDifficulty, util,
 };
 
+use super::GradualCursor;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = GradualDifficultySeries)]
+    pub type JsGradualDifficultySeries;
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const _: &'static str = r#"/**
+* The result of `GradualDifficulty.series`.
+*
+* Each array contains one value per included hitobject in the same order.
+* Skill values are only available for the mode they belong to.
+*
+* There are no skill values for osu!catch and osu!mania because their
+* difficulty attributes only contain the star rating, which is derived from
+* a single skill.
+*/
+export interface GradualDifficultySeries {
+    /**
+    * The start time of the hitobject in milliseconds.
+    *
+    * The time is the beatmap's time, i.e. it is not adjusted by the clock
+    * rate.
+    */
+    time: Float64Array;
+    /**
+    * The amount of processed hitobjects, i.e. `1` for the first hitobject.
+    */
+    position: Uint32Array;
+    /**
+    * The final star rating.
+    */
+    stars: Float64Array;
+    /**
+    * The difficulty of the aim skill.
+    *
+    * Only available for osu!standard.
+    */
+    aim?: Float64Array;
+    /**
+    * The difficulty of the speed skill.
+    *
+    * Only available for osu!standard.
+    */
+    speed?: Float64Array;
+    /**
+    * The difficulty of the flashlight skill.
+    *
+    * Only available for osu!standard.
+    */
+    flashlight?: Float64Array;
+    /**
+    * The difficulty of the stamina skill.
+    *
+    * Only available for osu!taiko.
+    */
+    stamina?: Float64Array;
+    /**
+    * The difficulty of the rhythm skill.
+    *
+    * Only available for osu!taiko.
+    */
+    rhythm?: Float64Array;
+    /**
+    * The difficulty of the color skill.
+    *
+    * Only available for osu!taiko.
+    */
+    color?: Float64Array;
+    /**
+    * The difficulty of the reading skill.
+    *
+    * Only available for osu!taiko.
+    */
+    reading?: Float64Array;
+}"#;
+
 /// Gradually calculate difficulty attributes after each hitobject.
 #[wasm_bindgen(js_name = GradualDifficulty)]
 pub struct JsGradualDifficulty {
-    inner: GradualDifficulty,
+    cursor: GradualCursor,
 }
 
 #[wasm_bindgen(js_class = GradualDifficulty)]
@@ -16,13 +96,13 @@ impl JsGradualDifficulty {
     #[wasm_bindgen(constructor)]
     pub fn new(difficulty: &JsDifficulty, map: &JsBeatmap) -> JsGradualDifficulty {
         Self {
-            inner: GradualDifficulty::new(difficulty.args.to_difficulty(), &map.inner),
+            cursor: GradualCursor::new(difficulty.args.to_difficulty(), &map.inner),
         }
     }
 
     /// Advances the iterator and returns the next attributes.
     pub fn next(&mut self) -> Option<JsDifficultyAttributes> {
-        self.inner.next().map(From::from)
+        self.cursor.nth(0).map(From::from)
     }
 
     /// Returns the `n`th attributes of the iterator.
@@ -30,18 +110,235 @@ impl JsGradualDifficulty {
     /// Note that the count starts from zero, so `nth(0)` returns the first
     /// value, `nth(1)` the second, and so on.
     pub fn nth(&mut self, n: usize) -> Option<JsDifficultyAttributes> {
-        self.inner.nth(n).map(From::from)
+        self.cursor.nth(n).map(From::from)
     }
 
     /// Advances the iterator to the end to collect all remaining attributes
     /// into a list and return them.
-    pub fn collect(self) -> Vec<JsDifficultyAttributes> {
-        self.inner.map(From::from).collect()
+    pub fn collect(mut self) -> Vec<JsDifficultyAttributes> {
+        let mut collected = Vec::with_capacity(self.cursor.n_remaining());
+
+        while let Some(attrs) = self.cursor.nth(0) {
+            collected.push(attrs.into());
+        }
+
+        collected
+    }
+
+    /// Move to the given amount of processed hitobjects and return the
+    /// attributes after the last of them.
+    ///
+    /// The position may also lie before the current one. Attributes of
+    /// previously processed hitobjects are stored so seeking backwards does
+    /// not require recalculation.
+    ///
+    /// Returns `undefined` for position `0`.
+    pub fn seek(&mut self, position: usize) -> Option<JsDifficultyAttributes> {
+        self.cursor.seek(position).map(From::from)
+    }
+
+    /// Move to the last hitobject that starts at or before the given time in
+    /// milliseconds and return the attributes after it.
+    ///
+    /// The time is the beatmap's time, i.e. it is not adjusted by the clock
+    /// rate. Same as for `seek`, the time may lie before the current one.
+    ///
+    /// Returns `undefined` if no hitobject starts at or before that time.
+    /// @throws Throws an error if the hitobjects' start times cannot be aligned with the gradual calculation
+    #[wasm_bindgen(js_name = advanceTo)]
+    pub fn advance_to(&mut self, time: f64) -> JsResult<Option<JsDifficultyAttributes>> {
+        let position = self.cursor.position_at(time)?;
+
+        Ok(self.cursor.seek(position).map(From::from))
+    }
+
+    /// Create a copy of this calculator at the current position.
+    ///
+    /// Both calculators share their calculated attributes so cloning is cheap
+    /// and can be used to snapshot a position.
+    #[wasm_bindgen(js_name = clone)]
+    pub fn js_clone(&self) -> JsGradualDifficulty {
+        Self {
+            cursor: self.cursor.clone(),
+        }
+    }
+
+    /// Calculate the attributes after every hitobject and return them as
+    /// columns of typed arrays.
+    ///
+    /// If a stride is given, only every `stride`th hitobject is included, as
+    /// well as the last one. The position of this calculator is not
+    /// modified.
+    /// @throws Throws an error if the stride is zero or if the hitobjects' start times cannot be aligned with the gradual calculation
+    pub fn series(&self, stride: Option<usize>) -> JsResult<JsGradualDifficultySeries> {
+        let stride = stride.unwrap_or(1);
+
+        if stride == 0 {
+            return Err(JsError::new("stride must be positive"));
+        }
+
+        let series = self.cursor.with_all(|attrs, start_times| {
+            let mut series = Series::new(attrs.first(), attrs.len().div_ceil(stride));
+            let last = attrs.len().saturating_sub(1);
+
+            let included = attrs
+                .iter()
+                .zip(start_times)
+                .enumerate()
+                .filter(|(i, _)| (i + 1) % stride == 0 || *i == last);
+
+            for (i, (attrs, start_time)) in included {
+                series.push(i + 1, *start_time, attrs);
+            }
+
+            series
+        })?;
+
+        Ok(series.into())
+    }
+
+    /// The amount of hitobjects that have been processed.
+    #[wasm_bindgen(getter)]
+    pub fn position(&self) -> usize {
+        self.cursor.position()
     }
 
     /// Returns the amount of remaining items.
     #[wasm_bindgen(js_name = nRemaining, getter)]
     pub fn n_remaining(&self) -> usize {
-        self.inner.len()
+        self.cursor.n_remaining()
+    }
+}
+
+struct Series {
+    time: Vec<f64>,
+    position: Vec<u32>,
+    stars: Vec<f64>,
+    skills: SeriesSkills,
+}
+
+enum SeriesSkills {
+    Osu {
+        aim: Vec<f64>,
+        speed: Vec<f64>,
+        flashlight: Vec<f64>,
+    },
+    Taiko {
+        stamina: Vec<f64>,
+        rhythm: Vec<f64>,
+        color: Vec<f64>,
+        reading: Vec<f64>,
+    },
+    None,
+}
+
+impl Series {
+    fn new(first: Option<&DifficultyAttributes>, capacity: usize) -> Self {
+        let skills = match first {
+            Some(DifficultyAttributes::Osu(_)) => SeriesSkills::Osu {
+                aim: Vec::with_capacity(capacity),
+                speed: Vec::with_capacity(capacity),
+                flashlight: Vec::with_capacity(capacity),
+            },
+            Some(DifficultyAttributes::Taiko(_)) => SeriesSkills::Taiko {
+                stamina: Vec::with_capacity(capacity),
+                rhythm: Vec::with_capacity(capacity),
+                color: Vec::with_capacity(capacity),
+                reading: Vec::with_capacity(capacity),
+            },
+            Some(DifficultyAttributes::Catch(_) | DifficultyAttributes::Mania(_)) | None => {
+                SeriesSkills::None
+            }
+        };
+
+        Self {
+            time: Vec::with_capacity(capacity),
+            position: Vec::with_capacity(capacity),
+            stars: Vec::with_capacity(capacity),
+            skills,
+        }
+    }
+
+    fn push(&mut self, position: usize, time: f64, attrs: &DifficultyAttributes) {
+        self.time.push(time);
+        self.position.push(position as u32);
+        self.stars.push(attrs.stars());
+
+        match (&mut self.skills, attrs) {
+            (
+                SeriesSkills::Osu {
+                    aim,
+                    speed,
+                    flashlight,
+                },
+                DifficultyAttributes::Osu(attrs),
+            ) => {
+                aim.push(attrs.aim);
+                speed.push(attrs.speed);
+                flashlight.push(attrs.flashlight);
+            }
+            (
+                SeriesSkills::Taiko {
+                    stamina,
+                    rhythm,
+                    color,
+                    reading,
+                },
+                DifficultyAttributes::Taiko(attrs),
+            ) => {
+                stamina.push(attrs.stamina);
+                rhythm.push(attrs.rhythm);
+                color.push(attrs.color);
+                reading.push(attrs.reading);
+            }
+            _ => {}
+        }
+    }
+}
+
+impl From<Series> for JsGradualDifficultySeries {
+    fn from(series: Series) -> Self {
+        let obj = js_sys::Object::new();
+        let obj_as_ext = obj.unchecked_ref::<util::ObjectExt>();
+
+        let set_f64 = |key: &'static str, values: &[f64]| {
+            obj_as_ext.set(
+                util::static_str_to_js(key),
+                js_sys::Float64Array::from(values).into(),
+            );
+        };
+
+        set_f64("time", &series.time);
+        obj_as_ext.set(
+            util::static_str_to_js("position"),
+            js_sys::Uint32Array::from(series.position.as_slice()).into(),
+        );
+        set_f64("stars", &series.stars);
+
+        match series.skills {
+            SeriesSkills::Osu {
+                aim,
+                speed,
+                flashlight,
+            } => {
+                set_f64("aim", &aim);
+                set_f64("speed", &speed);
+                set_f64("flashlight", &flashlight);
+            }
+            SeriesSkills::Taiko {
+                stamina,
+                rhythm,
+                color,
+                reading,
+            } => {
+                set_f64("stamina", &stamina);
+                set_f64("rhythm", &rhythm);
+                set_f64("color", &color);
+                set_f64("reading", &reading);
+            }
+            SeriesSkills::None => {}
+        }
+
+        JsValue::from(obj).unchecked_into()
     }
 }
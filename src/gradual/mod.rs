@@ -1,2 +1,292 @@
+use std::{cell::RefCell, rc::Rc};
+
+use rosu_map::section::hit_objects::{
+    BorrowedCurve, CurveBuffers, SliderEvent, SliderEventType, SliderEventsIter,
+};
+use rosu_pp::{
+    Beatmap, Difficulty, GradualDifficulty,
+    any::DifficultyAttributes,
+    model::{
+        hit_object::{HitObjectKind, Slider},
+        mode::GameMode,
+    },
+};
+
+use crate::{JsError, JsResult, hit_object};
+
 pub mod difficulty;
 pub mod performance;
+
+/// Position of a gradual calculation.
+///
+/// Difficulty attributes are stored after being calculated and shared
+/// between clones so that seeking backwards and cloning is cheap.
+#[derive(Clone)]
+pub struct GradualCursor {
+    attrs: Rc<RefCell<GradualAttributes>>,
+    /// The amount of processed hit objects.
+    pos: usize,
+}
+
+struct GradualAttributes {
+    inner: GradualDifficulty,
+    calculated: Vec<DifficultyAttributes>,
+    start_times: StartTimes,
+}
+
+/// The start times of the hit objects which are only determined once they
+/// are needed.
+enum StartTimes {
+    Pending(Box<Beatmap>),
+    Aligned(Vec<f64>),
+    /// The amount of start times did not match the amount of hit objects.
+    Misaligned(usize),
+}
+
+impl StartTimes {
+    /// Fails if the start times of the hit objects do not align with the
+    /// `n_objects` objects that rosu-pp processes gradually.
+    fn get(&mut self, n_objects: usize) -> JsResult<&[f64]> {
+        if let Self::Pending(map) = self {
+            let start_times = object_start_times(map);
+
+            // Without hit objects, rosu-pp still reports a length of one for
+            // some modes but doesn't produce any attributes.
+            *self = if start_times.len() == n_objects || (start_times.is_empty() && n_objects <= 1)
+            {
+                Self::Aligned(start_times)
+            } else {
+                Self::Misaligned(start_times.len())
+            };
+        }
+
+        match self {
+            Self::Aligned(start_times) => Ok(start_times),
+            Self::Misaligned(len) => Err(JsError::new(&format!(
+                "Found {len} hitobjects but expected {n_objects} for the gradual calculation"
+            ))),
+            Self::Pending(_) => unreachable!(),
+        }
+    }
+}
+
+impl GradualCursor {
+    pub fn new(difficulty: Difficulty, map: &Beatmap) -> Self {
+        let attrs = GradualAttributes {
+            inner: GradualDifficulty::new(difficulty, map),
+            calculated: Vec::new(),
+            start_times: StartTimes::Pending(Box::new(map.clone())),
+        };
+
+        Self {
+            attrs: Rc::new(RefCell::new(attrs)),
+            pos: 0,
+        }
+    }
+
+    /// The amount of processed hit objects.
+    pub const fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// The amount of hit objects in total.
+    pub fn len(&self) -> usize {
+        self.attrs.borrow().len()
+    }
+
+    pub fn n_remaining(&self) -> usize {
+        self.len() - self.pos
+    }
+
+    /// Process the next `n + 1` hit objects and return the attributes after
+    /// the last one.
+    pub fn nth(&mut self, n: usize) -> Option<DifficultyAttributes> {
+        let target = self.pos.saturating_add(n).saturating_add(1);
+
+        if target > self.len() {
+            self.pos = self.len();
+
+            return None;
+        }
+
+        self.seek(target)
+    }
+
+    /// Move to the given amount of processed hit objects and return the
+    /// attributes after the last one.
+    ///
+    /// The position is clamped to the amount of hit objects.
+    pub fn seek(&mut self, pos: usize) -> Option<DifficultyAttributes> {
+        self.pos = pos.min(self.len());
+
+        let idx = self.pos.checked_sub(1)?;
+        let mut attrs = self.attrs.borrow_mut();
+
+        while attrs.calculated.len() <= idx {
+            let next = attrs.inner.next()?;
+            attrs.calculated.push(next);
+        }
+
+        Some(attrs.calculated[idx].clone())
+    }
+
+    /// Calculate the attributes of all hit objects without moving the
+    /// position and pass them alongside their start times.
+    ///
+    /// Fails if the start times cannot be aligned with the hit objects.
+    pub fn with_all<R>(&self, f: impl FnOnce(&[DifficultyAttributes], &[f64]) -> R) -> JsResult<R> {
+        let mut attrs = self.attrs.borrow_mut();
+        let n_objects = attrs.len();
+
+        let GradualAttributes {
+            inner,
+            calculated,
+            start_times,
+        } = &mut *attrs;
+
+        let start_times = start_times.get(n_objects)?;
+        calculated.extend(inner);
+
+        Ok(f(calculated, start_times))
+    }
+
+    /// The amount of hit objects that start at or before the given time.
+    ///
+    /// Fails if the start times cannot be aligned with the hit objects.
+    pub fn position_at(&self, time: f64) -> JsResult<usize> {
+        let mut attrs = self.attrs.borrow_mut();
+        let n_objects = attrs.len();
+
+        let pos = attrs
+            .start_times
+            .get(n_objects)?
+            .partition_point(|start_time| *start_time <= time);
+
+        Ok(pos.min(n_objects))
+    }
+}
+
+impl GradualAttributes {
+    fn len(&self) -> usize {
+        self.calculated.len() + self.inner.len()
+    }
+}
+
+/// The start times of the hit objects that gradual calculators process, in
+/// order.
+///
+/// For osu!taiko these are only circles and for osu!catch only fruits and
+/// droplets, including the nested ones of juice streams.
+pub fn object_start_times(map: &Beatmap) -> Vec<f64> {
+    let mut start_times: Vec<_> = match map.mode {
+        GameMode::Osu | GameMode::Mania => map.hit_objects.iter().map(|h| h.start_time).collect(),
+        GameMode::Taiko => map
+            .hit_objects
+            .iter()
+            .filter(|h| h.is_circle())
+            .map(|h| h.start_time)
+            .collect(),
+        GameMode::Catch => {
+            let mut bufs = CurveBuffers::default();
+            let mut ticks = Vec::new();
+            let mut start_times = Vec::with_capacity(map.hit_objects.len());
+
+            for h in map.hit_objects.iter() {
+                match h.kind {
+                    HitObjectKind::Circle => start_times.push(h.start_time),
+                    HitObjectKind::Slider(ref slider) => juice_stream_start_times(
+                        map,
+                        h.start_time,
+                        slider,
+                        &mut bufs,
+                        &mut ticks,
+                        &mut start_times,
+                    ),
+                    // Banana showers are not processed
+                    HitObjectKind::Spinner(_) | HitObjectKind::Hold(_) => {}
+                }
+            }
+
+            start_times
+        }
+    };
+
+    start_times.sort_by(f64::total_cmp);
+
+    start_times
+}
+
+const JUICE_STREAM_BASE_SCORING_DIST: f64 = 100.0;
+
+/// Mirrors how rosu-pp generates the fruits and droplets of a juice stream.
+///
+/// rosu-pp does not expose the nested objects so any divergence is caught by
+/// comparing the amount of start times in [`StartTimes::get`].
+fn juice_stream_start_times(
+    map: &Beatmap,
+    start_time: f64,
+    slider: &Slider,
+    bufs: &mut CurveBuffers,
+    ticks: &mut Vec<SliderEvent>,
+    start_times: &mut Vec<f64>,
+) {
+    let beat_len = hit_object::point_at(&map.timing_points, start_time, |point| point.time)
+        .or_else(|| map.timing_points.first())
+        .map_or(1000.0, |point| point.beat_len);
+
+    let slider_velocity =
+        hit_object::point_at(&map.difficulty_points, start_time, |point| point.time)
+            .map_or(1.0, |point| point.slider_velocity);
+
+    // Same as `hit_object::slider_velocity` but with rosu-pp's conversion
+    // to `f32` so that tick times match exactly
+    let bpm_multiplier = if slider_velocity > 0.0 {
+        f64::from(((100.0 / slider_velocity) as f32).clamp(10.0, 10_000.0)) / 100.0
+    } else {
+        1.0
+    };
+
+    let velocity =
+        JUICE_STREAM_BASE_SCORING_DIST * map.slider_multiplier / (beat_len * bpm_multiplier);
+    let scoring_dist = velocity * beat_len;
+
+    let tick_dist_multiplier = if map.version < 8 {
+        slider_velocity.recip()
+    } else {
+        1.0
+    };
+
+    let tick_dist = scoring_dist / map.slider_tick_rate * tick_dist_multiplier;
+
+    let dist = BorrowedCurve::new(
+        GameMode::Catch,
+        &slider.control_points,
+        slider.expected_dist,
+        bufs,
+    )
+    .dist();
+
+    let span_count = slider.span_count();
+    let duration = span_count as f64 * dist / velocity;
+    let span_duration = duration / span_count as f64;
+
+    let events = SliderEventsIter::new(
+        start_time,
+        span_duration,
+        velocity,
+        tick_dist,
+        dist,
+        span_count as i32,
+        ticks,
+    );
+
+    for event in events {
+        match event.kind {
+            SliderEventType::Head
+            | SliderEventType::Tick
+            | SliderEventType::Repeat
+            | SliderEventType::Tail => start_times.push(event.time),
+            SliderEventType::LastTick => {}
+        }
+    }
+}
@@ -1,24 +1,47 @@
-use rosu_pp::GradualPerformance;
+use rosu_pp::{
+    Difficulty, Performance,
+    any::{DifficultyAttributes, PerformanceAttributes, ScoreState},
+    model::mode::GameMode,
+};
 use wasm_bindgen::prelude::wasm_bindgen;
 
 use crate::{
-    attributes::performance::JsPerformanceAttributes, beatmap::JsBeatmap,
-    deserializer::JsDeserializer, difficulty::JsDifficulty, error::JsResult,
+    args::gradual::{HitEvent, JsHitEvent, JsHitEventList, JsHitResult},
+    attributes::performance::JsPerformanceAttributes,
+    beatmap::JsBeatmap,
+    deserializer::JsDeserializer,
+    difficulty::JsDifficulty,
+    error::JsResult,
     score_state::JsScoreState,
+    util,
 };
 
+use super::GradualCursor;
+
 /// Gradually calculate performance attributes after each hitresult.
 #[wasm_bindgen(js_name = GradualPerformance)]
 pub struct JsGradualPerformance {
-    inner: GradualPerformance,
+    cursor: GradualCursor,
+    difficulty: Difficulty,
+    mode: GameMode,
+    /// Accumulated through hit events or the last passed state.
+    state: ScoreState,
+    /// Current combo of the accumulated state.
+    combo: u32,
 }
 
 #[wasm_bindgen(js_class = GradualPerformance)]
 impl JsGradualPerformance {
     #[wasm_bindgen(constructor)]
     pub fn new(difficulty: &JsDifficulty, map: &JsBeatmap) -> JsGradualPerformance {
+        let difficulty = difficulty.args.to_difficulty();
+
         Self {
-            inner: GradualPerformance::new(difficulty.args.to_difficulty(), &map.inner),
+            cursor: GradualCursor::new(difficulty.clone(), &map.inner),
+            difficulty,
+            mode: map.inner.mode,
+            state: ScoreState::new(),
+            combo: 0,
         }
     }
 
@@ -26,8 +49,9 @@ impl JsGradualPerformance {
     /// for the resulting score state.
     pub fn next(&mut self, state: &JsScoreState) -> JsResult<Option<JsPerformanceAttributes>> {
         let state = JsScoreState::deserialize(JsDeserializer::from_ref(state))?;
+        self.set_state(&state);
 
-        Ok(self.inner.next(state).map(From::from))
+        Ok(self.nth_with(state, 0).map(From::from))
     }
 
     /// Process everything up to the next `n`th hitobject and calculate the
@@ -41,13 +65,194 @@ impl JsGradualPerformance {
         n: usize,
     ) -> JsResult<Option<JsPerformanceAttributes>> {
         let state = JsScoreState::deserialize(JsDeserializer::from_ref(state))?;
+        self.set_state(&state);
+
+        Ok(self.nth_with(state, n).map(From::from))
+    }
+
+    /// Process the next hit object with the given judgement and calculate the
+    /// performance attributes for the accumulated score state.
+    ///
+    /// The score state is accumulated internally so, unlike for `next`, only
+    /// the current hit object's judgement is required. Hit events and score
+    /// states can be mixed; passing a score state to `next` or `nth`
+    /// replaces the accumulated state.
+    /// @throws Throws an error if the hit event is invalid
+    #[wasm_bindgen(js_name = nextHit)]
+    pub fn next_hit(&mut self, hit: &JsHitEvent) -> JsResult<Option<JsPerformanceAttributes>> {
+        let hit = util::from_value::<HitEvent>(hit)?;
+
+        if self.cursor.n_remaining() == 0 {
+            return Ok(None);
+        }
+
+        self.apply_hit(&hit);
+        let state = self.state.clone();
+
+        Ok(self
+            .nth_with(state.clone(), 0)
+            .map(|attrs| JsPerformanceAttributes::new(attrs, state)))
+    }
+
+    /// Process a hit object for each hit event and return the pp after each
+    /// of them.
+    ///
+    /// Stops early if there are no remaining hit objects so the returned list
+    /// may be shorter than the given one.
+    /// @throws Throws an error if the hit events are invalid
+    #[wasm_bindgen(js_name = nextHits)]
+    pub fn next_hits(&mut self, hits: &JsHitEventList) -> JsResult<Vec<f64>> {
+        let hits = util::from_value::<Vec<HitEvent>>(hits)?;
+        let mut pps = Vec::with_capacity(hits.len().min(self.cursor.n_remaining()));
+
+        for hit in hits.iter() {
+            if self.cursor.n_remaining() == 0 {
+                break;
+            }
+
+            self.apply_hit(hit);
+
+            if let Some(attrs) = self.nth_with(self.state.clone(), 0) {
+                pps.push(attrs.pp());
+            }
+        }
+
+        Ok(pps)
+    }
+
+    /// Move to the given amount of processed hitobjects and calculate the
+    /// performance attributes for the given score state.
+    ///
+    /// The position may also lie before the current one. Difficulty
+    /// attributes of previously processed hitobjects are stored so seeking
+    /// backwards does not require recalculation.
+    ///
+    /// Returns `undefined` for position `0`.
+    /// @throws Throws an error if the score state is invalid
+    pub fn seek(
+        &mut self,
+        position: usize,
+        state: &JsScoreState,
+    ) -> JsResult<Option<JsPerformanceAttributes>> {
+        let state = JsScoreState::deserialize(JsDeserializer::from_ref(state))?;
+        self.set_state(&state);
+        let attrs = self.cursor.seek(position);
 
-        Ok(self.inner.nth(state, n).map(From::from))
+        Ok(attrs.map(|attrs| self.calculate(attrs, state).into()))
+    }
+
+    /// Move to the last hitobject that starts at or before the given time in
+    /// milliseconds and calculate the performance attributes for the given
+    /// score state.
+    ///
+    /// The time is the beatmap's time, i.e. it is not adjusted by the clock
+    /// rate. Same as for `seek`, the time may lie before the current one.
+    ///
+    /// Returns `undefined` if no hitobject starts at or before that time.
+    /// @throws Throws an error if the score state is invalid or if the hitobjects' start times cannot be aligned with the gradual calculation
+    #[wasm_bindgen(js_name = advanceTo)]
+    pub fn advance_to(
+        &mut self,
+        time: f64,
+        state: &JsScoreState,
+    ) -> JsResult<Option<JsPerformanceAttributes>> {
+        let position = self.cursor.position_at(time)?;
+
+        self.seek(position, state)
+    }
+
+    /// Create a copy of this calculator at the current position, including
+    /// the accumulated score state.
+    ///
+    /// Both calculators share their calculated difficulty attributes so
+    /// cloning is cheap and can be used to snapshot a position.
+    #[wasm_bindgen(js_name = clone)]
+    pub fn js_clone(&self) -> JsGradualPerformance {
+        Self {
+            cursor: self.cursor.clone(),
+            difficulty: self.difficulty.clone(),
+            mode: self.mode,
+            state: self.state.clone(),
+            combo: self.combo,
+        }
+    }
+
+    /// The amount of hitobjects that have been processed.
+    #[wasm_bindgen(getter)]
+    pub fn position(&self) -> usize {
+        self.cursor.position()
+    }
+
+    /// The score state that was accumulated so far.
+    #[wasm_bindgen(getter)]
+    pub fn state(&self) -> JsScoreState {
+        self.state.clone().into()
     }
 
     /// Returns the amount of remaining items.
     #[wasm_bindgen(js_name = nRemaining, getter)]
     pub fn n_remaining(&self) -> usize {
-        self.inner.len()
+        self.cursor.n_remaining()
+    }
+}
+
+impl JsGradualPerformance {
+    fn nth_with(&mut self, state: ScoreState, n: usize) -> Option<PerformanceAttributes> {
+        let attrs = self.cursor.nth(n)?;
+
+        Some(self.calculate(attrs, state))
+    }
+
+    /// Same as rosu-pp's gradual performance calculation.
+    fn calculate(&self, attrs: DifficultyAttributes, state: ScoreState) -> PerformanceAttributes {
+        Performance::new(attrs)
+            .state(state)
+            .difficulty(self.difficulty.clone())
+            .passed_objects(self.cursor.position() as u32)
+            .calculate()
+    }
+
+    fn set_state(&mut self, state: &ScoreState) {
+        self.state = state.clone();
+
+        // The current combo is unknown so the best guess is the max combo
+        self.combo = state.max_combo;
+    }
+
+    fn apply_hit(&mut self, hit: &HitEvent) {
+        let state = &mut self.state;
+
+        match (hit.result, self.mode) {
+            (JsHitResult::Perfect, GameMode::Mania) => state.n_geki += 1,
+            (JsHitResult::Perfect | JsHitResult::Great, _) => state.n300 += 1,
+            (JsHitResult::Good, GameMode::Mania) => state.n_katu += 1,
+            (JsHitResult::Good | JsHitResult::Ok, _) => state.n100 += 1,
+            (JsHitResult::Meh, _) => state.n50 += 1,
+            (JsHitResult::Miss, _) => state.misses += 1,
+        }
+
+        let mut combo_hits = 1;
+
+        match self.mode {
+            GameMode::Osu => {
+                state.osu_large_tick_hits += hit.large_tick_hits;
+                state.osu_small_tick_hits += hit.small_tick_hits;
+                state.slider_end_hits += u32::from(hit.slider_end_hit);
+                combo_hits += hit.large_tick_hits + u32::from(hit.slider_end_hit);
+            }
+            GameMode::Catch => {
+                state.n50 += hit.small_tick_hits;
+                state.n_katu += hit.small_tick_misses;
+            }
+            GameMode::Taiko | GameMode::Mania => {}
+        }
+
+        self.combo = match hit.combo {
+            Some(combo) => combo,
+            None if hit.result == JsHitResult::Miss => 0,
+            None => self.combo + combo_hits,
+        };
+
+        state.max_combo = state.max_combo.max(self.combo);
     }
 }
@@ -0,0 +1,333 @@
+use rosu_map::{
+    section::hit_objects::{BorrowedCurve, CurveBuffers, SplineType},
+    util::StrExt,
+};
+use rosu_pp::{
+    Beatmap,
+    model::{
+        hit_object::{HitObject, HitObjectKind, HitSoundType, PathType, Slider},
+        mode::GameMode,
+    },
+};
+use wasm_bindgen::prelude::*;
+
+use crate::util;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = HitObject)]
+    pub type JsHitObject;
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const _: &'static str = r#"/**
+* A hitobject of a beatmap.
+*/
+export interface HitObject {
+    /**
+    * The kind of hitobject.
+    */
+    kind: HitObjectKind;
+    /**
+    * The x-coordinate of the hitobject.
+    */
+    x: number;
+    /**
+    * The y-coordinate of the hitobject.
+    */
+    y: number;
+    /**
+    * The start time in milliseconds.
+    */
+    startTime: number;
+    /**
+    * The end time in milliseconds.
+    *
+    * Equal to `startTime` for circles.
+    */
+    endTime: number;
+    /**
+    * Hitsound bitflags i.e. `1` for normal, `2` for whistle, `4` for finish,
+    * and `8` for clap.
+    */
+    hitSound: number;
+    /**
+    * The amount of repeats.
+    *
+    * Only available for sliders.
+    */
+    repeats?: number;
+    /**
+    * The expected length of the slider path in osu!pixels as specified in
+    * the `.osu` file.
+    *
+    * Only available for sliders.
+    */
+    pixelLength?: number;
+    /**
+    * The control points of the slider path, relative to the slider's head.
+    *
+    * Only available for sliders.
+    */
+    controlPoints?: SliderControlPoint[];
+    /**
+    * Hitsound bitflags for each node i.e. head, repeats, and tail.
+    *
+    * Only available for sliders.
+    */
+    nodeSounds?: number[];
+    /**
+    * Custom sample info as specified in the `.osu` file.
+    *
+    * Unavailable if the hitobjects no longer correspond to the `.osu` file,
+    * e.g. after converting to mania.
+    */
+    samples?: HitSamples;
+}
+
+/**
+* Custom sample info of a hitobject.
+*
+* Sample sets are `0` to use the active sample point's set, `1` for normal,
+* `2` for soft, and `3` for drum.
+*/
+export interface HitSamples {
+    /**
+    * The sample set of the normal sound.
+    */
+    normalSet: number;
+    /**
+    * The sample set of the whistle, finish, and clap sounds.
+    */
+    additionSet: number;
+    /**
+    * The custom sample index; `0` to use the active sample point's index.
+    */
+    index: number;
+    /**
+    * The sample volume; `0` to use the active sample point's volume.
+    */
+    volume: number;
+    /**
+    * The custom filename of the normal sound; empty if unspecified.
+    */
+    filename: string;
+}
+
+/**
+* A control point of a slider path.
+*/
+export interface SliderControlPoint {
+    /**
+    * The x-coordinate relative to the slider's head.
+    */
+    x: number;
+    /**
+    * The y-coordinate relative to the slider's head.
+    */
+    y: number;
+    /**
+    * The path type starting at this point if it starts a new segment, i.e.
+    * `"B"` or `"B<degree>"` for bezier, `"C"` for catmull, `"L"` for linear,
+    * and `"P"` for perfect curves.
+    */
+    pathType?: string;
+}"#;
+
+/// The kind of a hitobject.
+#[wasm_bindgen(js_name = HitObjectKind)]
+#[derive(Copy, Clone)]
+pub enum JsHitObjectKind {
+    Circle = 0,
+    Slider = 1,
+    Spinner = 2,
+    Hold = 3,
+}
+
+impl From<&HitObjectKind> for JsHitObjectKind {
+    fn from(kind: &HitObjectKind) -> Self {
+        match kind {
+            HitObjectKind::Circle => Self::Circle,
+            HitObjectKind::Slider(_) => Self::Slider,
+            HitObjectKind::Spinner(_) => Self::Spinner,
+            HitObjectKind::Hold(_) => Self::Hold,
+        }
+    }
+}
+
+/// Custom sample info of a hitobject i.e. the `hitSample` field of its line
+/// in a `.osu` file.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct HitSamples {
+    pub normal_set: i32,
+    pub addition_set: i32,
+    pub index: i32,
+    pub volume: i32,
+    pub filename: String,
+}
+
+impl HitSamples {
+    /// Parse the colon-separated `normalSet:additionSet:index:volume:filename`.
+    ///
+    /// Missing or invalid values fall back to their default.
+    pub fn parse(s: &str) -> Self {
+        let mut split = s.splitn(5, ':');
+        let mut next = || split.next().map_or(0, |s| s.parse_num().unwrap_or(0));
+
+        let normal_set = next();
+        let addition_set = next();
+        let index = next();
+        let volume = next().max(0);
+
+        Self {
+            normal_set,
+            addition_set,
+            index,
+            volume,
+            filename: split.next().unwrap_or_default().to_owned(),
+        }
+    }
+
+    fn to_js(&self) -> JsValue {
+        let obj = js_sys::Object::new();
+        let obj_as_ext = obj.unchecked_ref::<util::ObjectExt>();
+
+        let set = |key, value: JsValue| obj_as_ext.set(util::static_str_to_js(key), value);
+
+        set("normalSet", self.normal_set.into());
+        set("additionSet", self.addition_set.into());
+        set("index", self.index.into());
+        set("volume", self.volume.into());
+        set("filename", self.filename.as_str().into());
+
+        obj.into()
+    }
+}
+
+impl JsHitObject {
+    pub fn new(
+        map: &Beatmap,
+        h: &HitObject,
+        hit_sound: HitSoundType,
+        samples: Option<&HitSamples>,
+        bufs: &mut CurveBuffers,
+    ) -> Self {
+        let obj = js_sys::Object::new();
+        let obj_as_ext = obj.unchecked_ref::<util::ObjectExt>();
+
+        let set = |key, value: JsValue| obj_as_ext.set(util::static_str_to_js(key), value);
+
+        set("kind", (JsHitObjectKind::from(&h.kind) as u8).into());
+        set("x", h.pos.x.into());
+        set("y", h.pos.y.into());
+        set("startTime", h.start_time.into());
+        set("endTime", end_time(map, h, bufs).into());
+        set("hitSound", u8::from(hit_sound).into());
+
+        if let HitObjectKind::Slider(ref slider) = h.kind {
+            let control_points = slider
+                .control_points
+                .iter()
+                .map(|point| {
+                    let obj = js_sys::Object::new();
+                    let obj_as_ext = obj.unchecked_ref::<util::ObjectExt>();
+
+                    let set =
+                        |key, value: JsValue| obj_as_ext.set(util::static_str_to_js(key), value);
+
+                    set("x", point.pos.x.into());
+                    set("y", point.pos.y.into());
+
+                    if let Some(path_type) = point.path_type {
+                        set("pathType", path_type_str(path_type).into());
+                    }
+
+                    obj
+                })
+                .collect::<js_sys::Array>();
+
+            let node_sounds = slider
+                .node_sounds
+                .iter()
+                .map(|sound| JsValue::from(u8::from(*sound)))
+                .collect::<js_sys::Array>();
+
+            set("repeats", slider.repeats.into());
+
+            if let Some(expected_dist) = slider.expected_dist {
+                set("pixelLength", expected_dist.into());
+            }
+
+            set("controlPoints", control_points.into());
+            set("nodeSounds", node_sounds.into());
+        }
+
+        if let Some(samples) = samples {
+            set("samples", samples.to_js());
+        }
+
+        JsValue::from(obj).into()
+    }
+}
+
+/// The end time of a hitobject, including sliders.
+pub fn end_time(map: &Beatmap, h: &HitObject, bufs: &mut CurveBuffers) -> f64 {
+    match h.kind {
+        HitObjectKind::Circle => h.start_time,
+        HitObjectKind::Slider(ref slider) => h.start_time + slider_duration(map, h, slider, bufs),
+        HitObjectKind::Spinner(ref spinner) => h.start_time + spinner.duration,
+        HitObjectKind::Hold(ref hold) => h.start_time + hold.duration,
+    }
+}
+
+fn slider_duration(map: &Beatmap, h: &HitObject, slider: &Slider, bufs: &mut CurveBuffers) -> f64 {
+    let dist = match slider.expected_dist {
+        Some(dist) if dist > 0.0 => dist,
+        _ => BorrowedCurve::new(map.mode, &slider.control_points, None, bufs).dist(),
+    };
+
+    slider.span_count() as f64 * dist / slider_velocity(map, h.start_time)
+}
+
+/// The velocity in osu!pixels per millisecond of a slider starting at the
+/// given time.
+pub fn slider_velocity(map: &Beatmap, time: f64) -> f64 {
+    const BASE_SCORING_DIST: f64 = 100.0;
+
+    let beat_len = point_at(&map.timing_points, time, |point| point.time)
+        .or_else(|| map.timing_points.first())
+        .map_or(1000.0, |point| point.beat_len);
+
+    let slider_velocity = point_at(&map.difficulty_points, time, |point| point.time)
+        .map_or(1.0, |point| point.slider_velocity);
+
+    // Mirrors the precision loss of legacy slider velocities
+    let max_bpm_multiplier = match map.mode {
+        GameMode::Osu | GameMode::Catch => 10_000.0,
+        GameMode::Taiko | GameMode::Mania => 1000.0,
+    };
+
+    let bpm_multiplier = (100.0 / slider_velocity).clamp(10.0, max_bpm_multiplier) / 100.0;
+
+    BASE_SCORING_DIST * map.slider_multiplier / (beat_len * bpm_multiplier)
+}
+
+/// Find the control point that is active at the given time.
+pub fn point_at<T>(points: &[T], time: f64, get_time: impl Fn(&T) -> f64) -> Option<&T> {
+    points
+        .binary_search_by(|probe| get_time(probe).total_cmp(&time))
+        .map_or_else(|i| i.checked_sub(1), Some)
+        .map(|i| &points[i])
+}
+
+fn path_type_str(path_type: PathType) -> String {
+    match path_type.kind {
+        SplineType::Catmull => "C".to_owned(),
+        SplineType::BSpline => match path_type.degree {
+            Some(degree) => format!("B{degree}"),
+            None => "B".to_owned(),
+        },
+        SplineType::Linear => "L".to_owned(),
+        SplineType::PerfectCurve => "P".to_owned(),
+    }
+}
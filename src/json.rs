@@ -0,0 +1,146 @@
+use wasm_bindgen::prelude::*;
+
+use crate::{
+    JsError, JsResult,
+    attributes::{
+        beatmap::JsBeatmapAttributes, difficulty::JsDifficultyAttributes,
+        performance::JsPerformanceAttributes,
+    },
+    strains::JsStrains,
+    util,
+};
+
+/// Version of the JSON representation of result classes.
+///
+/// Has to be incremented whenever fields are renamed or removed so that
+/// outdated JSON can be detected.
+pub const SCHEMA_VERSION: u32 = 1;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = Object)]
+    pub type JsJson;
+}
+
+/// Let Node.js display result classes through their JSON representation,
+/// e.g. in `console.log`.
+///
+/// The hook is added to the class prototypes through throwaway instances.
+/// Only Node.js uses the hook so it is not added elsewhere, e.g. in browsers
+/// where a content security policy might forbid creating functions from
+/// source.
+pub fn add_inspect_hooks() {
+    if !is_node() {
+        return;
+    }
+
+    let inspect = js_sys::Function::new_no_args(
+        "return Object.assign(Object.create({ constructor: this.constructor }), this.toJSON());",
+    );
+
+    let symbol = js_sys::Symbol::for_("nodejs.util.inspect.custom");
+
+    let descriptor = js_sys::Object::new();
+    descriptor
+        .unchecked_ref::<util::ObjectExt>()
+        .set(util::static_str_to_js("value"), inspect.into());
+
+    let instances = [
+        JsValue::from(JsBeatmapAttributes::default()),
+        JsValue::from(JsDifficultyAttributes::default()),
+        JsValue::from(JsPerformanceAttributes::default()),
+        JsValue::from(JsStrains::default()),
+    ];
+
+    for instance in instances {
+        let proto = js_sys::Object::get_prototype_of(&instance);
+
+        // Fails without throwing if the hook was already added
+        let _ = js_sys::Reflect::define_property(&proto, &symbol, &descriptor);
+
+        if let Ok(free) = js_sys::Reflect::get(&instance, &util::static_str_to_js("free")) {
+            let _ = free.unchecked_into::<js_sys::Function>().call0(&instance);
+        }
+    }
+}
+
+/// Whether the code runs in Node.js.
+fn is_node() -> bool {
+    js_sys::Reflect::get(&js_sys::global(), &util::static_str_to_js("process"))
+        .and_then(|process| js_sys::Reflect::get(&process, &util::static_str_to_js("versions")))
+        .and_then(|versions| js_sys::Reflect::get(&versions, &util::static_str_to_js("node")))
+        .is_ok_and(|node| node.is_string())
+}
+
+/// Plain JS object that represents a result class in JSON.
+pub struct JsonObject {
+    obj: js_sys::Object,
+}
+
+impl JsonObject {
+    pub fn new() -> Self {
+        Self {
+            obj: js_sys::Object::new(),
+        }
+    }
+
+    /// Create an object that contains the schema version.
+    pub fn versioned() -> Self {
+        let this = Self::new();
+        this.set("version", SCHEMA_VERSION);
+
+        this
+    }
+
+    pub fn set(&self, key: &'static str, value: impl Into<JsValue>) {
+        self.obj
+            .unchecked_ref::<util::ObjectExt>()
+            .set(util::static_str_to_js(key), value.into());
+    }
+
+    /// Only set the value if it's available so the key is omitted otherwise.
+    pub fn set_opt(&self, key: &'static str, value: Option<impl Into<JsValue>>) {
+        if let Some(value) = value {
+            self.set(key, value);
+        }
+    }
+
+    /// Set the values as plain array because typed arrays are not serialized
+    /// as arrays through `JSON.stringify`.
+    pub fn set_values(&self, key: &'static str, values: Option<&[f64]>) {
+        if let Some(values) = values {
+            let array: js_sys::Array = values.iter().copied().map(JsValue::from).collect();
+            self.set(key, array);
+        }
+    }
+}
+
+impl From<JsonObject> for JsJson {
+    fn from(obj: JsonObject) -> Self {
+        JsValue::from(obj.obj).unchecked_into()
+    }
+}
+
+/// Ensure that the JSON was created with a compatible schema version.
+pub fn check_version(json: &JsJson) -> JsResult<()> {
+    #[derive(serde::Deserialize)]
+    #[serde(rename = "Object")]
+    struct Versioned {
+        version: u32,
+    }
+
+    let Versioned { version } = util::from_value::<Versioned>(json)?;
+
+    if version == 0 || version > SCHEMA_VERSION {
+        return Err(JsError::new(&format!(
+            "Unsupported schema version {version}; expected at most {SCHEMA_VERSION}"
+        )));
+    }
+
+    Ok(())
+}
+
+/// Stringify the JSON representation of a result class.
+pub fn stringify(json: &JsJson) -> String {
+    js_sys::JSON::stringify(json).map_or_else(|_| String::new(), String::from)
+}
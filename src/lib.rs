@@ -1,19 +1,36 @@
+mod analysis;
 mod args;
 mod attributes;
 mod beatmap;
+mod budget;
+mod cache;
+mod control_point;
 mod deserializer;
 mod difficulty;
 mod error;
 mod gradual;
+mod hit_object;
+mod json;
 mod mode;
 mod mods;
+mod parallel;
 mod performance;
+mod replay;
 mod score_state;
+mod solve;
 mod strains;
+#[cfg(feature = "threads")]
+mod threads;
 mod util;
+mod validation;
 
 use self::error::{JsError, JsResult};
 
+#[wasm_bindgen::prelude::wasm_bindgen(start, skip_typescript)]
+fn start() {
+    json::add_inspect_hooks();
+}
+
 #[wasm_bindgen::prelude::wasm_bindgen]
 #[cfg(debug_assertions)]
 extern "C" {
@@ -0,0 +1,25 @@
+/// Map each item, in parallel if the `threads` feature is enabled.
+///
+/// The thread pool has to be initialized through `initThreadPool`
+/// beforehand. The calling thread blocks until all items are mapped so,
+/// since the browser's main thread cannot block, it must be a Web Worker.
+#[cfg(feature = "threads")]
+pub fn map<T, U, F>(items: Vec<T>, f: F) -> Vec<U>
+where
+    T: Send,
+    U: Send,
+    F: Fn(T) -> U + Send + Sync,
+{
+    use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+    items.into_par_iter().map(f).collect()
+}
+
+/// Map each item, in parallel if the `threads` feature is enabled.
+#[cfg(not(feature = "threads"))]
+pub fn map<T, U, F>(items: Vec<T>, f: F) -> Vec<U>
+where
+    F: Fn(T) -> U,
+{
+    items.into_iter().map(f).collect()
+}
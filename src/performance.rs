@@ -1,16 +1,54 @@
-use rosu_pp::Performance;
-use wasm_bindgen::prelude::wasm_bindgen;
+use rosu_pp::{Performance, any::DifficultyAttributes, model::mode::GameMode};
+use wasm_bindgen::{JsCast, JsValue, prelude::wasm_bindgen};
 
 use crate::{
+    JsError, JsResult,
+    args::difficulty::DifficultyArgs,
     args::performance::{
-        JsHitResultPriority, JsMapOrAttributes, JsPerformanceArgs, MapOrAttrs, PerformanceArgs,
+        JsHitResultPriority, JsMapOrAttributes, JsPerformanceArgs, JsPerformanceArgsList,
+        JsPerformanceScenariosArgs, JsSolveForPpArgs, MapOrAttrs, PerformanceArgs,
+        PerformanceScenariosArgs, SolveForPpArgs,
     },
     attributes::performance::JsPerformanceAttributes,
+    beatmap::JsBeatmap,
+    budget::{self, Budget, JsCalculationBudget},
+    cache::{JsDifficultyCache, JsDifficultyCacheRef},
     deserializer::JsDeserializer,
     mods::JsGameMods,
-    util, JsResult,
+    replay::JsReplay,
+    score_state::JsScoreState,
+    solve::{self, JsPpSolution},
+    util,
+    validation::{JsScoreValidation, ScoreFields, Validation},
 };
 
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = PerformanceScenarios)]
+    pub type JsPerformanceScenarios;
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const _: &'static str = r#"/**
+* The result of `Performance.calculateScenarios`.
+*/
+export interface PerformanceScenarios {
+    /**
+    * The performance attributes of the score itself.
+    */
+    current: PerformanceAttributes;
+    /**
+    * The performance attributes of the score if it were a full combo, i.e.
+    * misses are considered to be 300s, all slider ticks and ends are hit,
+    * and combo is the maximum combo.
+    */
+    fc: PerformanceAttributes;
+    /**
+    * The pp of a full combo for each given accuracy in the same order.
+    */
+    accuracies: Float64Array;
+}"#;
+
 /// Builder for a performance calculation.
 #[wasm_bindgen(js_name = Performance)]
 pub struct JsPerformance {
@@ -41,24 +79,253 @@ impl JsPerformance {
     /// However, be careful that the passed attributes have been calculated
     /// for the same difficulty settings like mods, clock rate, beatmap,
     /// custom ar, ... otherwise the final attributes will be incorrect.
+    ///
+    /// If a beatmap is passed and a `DifficultyCache` was specified,
+    /// previously calculated attributes for the same beatmap and settings are
+    /// reused.
     pub fn calculate(&mut self, args: &JsMapOrAttributes) -> JsResult<JsPerformanceAttributes> {
-        let map_or_attrs = MapOrAttrs::from_value(args)?;
-        let map;
+        Self::calculate_with(&self.args, args, None)
+    }
 
-        let mut perf = match map_or_attrs {
-            MapOrAttrs::Map(map_) => {
-                map = map_;
+    /// Calculate performance attributes on a beatmap while calculating its
+    /// difficulty attributes within the given budget.
+    ///
+    /// See `Difficulty.calculateWithBudget`.
+    /// @throws Throws a `CalculationAbortedError` if the budget is exceeded or an error if the arguments are invalid
+    #[wasm_bindgen(js_name = calculateWithBudget)]
+    pub fn calculate_with_budget(
+        &self,
+        map: &JsBeatmap,
+        budget: &JsCalculationBudget,
+    ) -> JsResult<JsPerformanceAttributes> {
+        let budget = Budget::from_value(Some(budget))?;
+        let attrs = budget::calculate(&self.args.difficulty_args(), map, budget)?;
+
+        Ok(Self::calculate_from_attrs(&self.args, attrs))
+    }
 
-                Performance::new(&map.inner)
-            }
-            MapOrAttrs::Attrs(attrs) => Performance::new(attrs),
+    /// Same as `calculateWithBudget` but the difficulty calculation yields to
+    /// the event loop after every few hitobjects.
+    ///
+    /// See `Difficulty.calculateAsync`.
+    /// @throws Throws an error if the arguments are invalid
+    #[wasm_bindgen(
+        js_name = calculateAsync,
+        unchecked_return_type = "Promise<PerformanceAttributes>"
+    )]
+    pub fn calculate_async(
+        &self,
+        map: &JsBeatmap,
+        budget: Option<JsCalculationBudget>,
+    ) -> JsResult<js_sys::Promise> {
+        let budget = Budget::from_value(budget.as_ref())?;
+        let calculation = budget::calculate_async(&self.args.difficulty_args(), map, budget);
+        let perf_args = self.args.clone();
+
+        let promise = wasm_bindgen_futures::future_to_promise(async move {
+            calculation
+                .await
+                .map(|attrs| Self::calculate_from_attrs(&perf_args, attrs).into())
+                .map_err(JsValue::from)
+        });
+
+        Ok(promise)
+    }
+
+    /// Calculate performance attributes for a replay.
+    ///
+    /// Mods, hitresults, and combo are taken from the replay and override the
+    /// corresponding arguments of this calculator. Other arguments like
+    /// clock rate or custom ar remain in effect.
+    ///
+    /// The same caveats regarding passed attributes apply as for
+    /// `Performance.calculate`.
+    #[wasm_bindgen(js_name = calculateReplay)]
+    pub fn calculate_replay(
+        &mut self,
+        args: &JsMapOrAttributes,
+        replay: &JsReplay,
+    ) -> JsResult<JsPerformanceAttributes> {
+        let mut perf_args = self.args.clone();
+        replay.apply(&mut perf_args);
+
+        Self::calculate_with(&perf_args, args, Some(replay.inner.mode))
+    }
+
+    /// Calculate performance attributes for multiple sets of arguments on the
+    /// same beatmap or attributes.
+    ///
+    /// Returns the attributes in the same order as the given arguments.
+    ///
+    /// If a beatmap is passed, difficulty attributes are only calculated once
+    /// for each distinct set of difficulty settings like mods or clock rate.
+    /// With the `threads` feature, they are calculated in parallel.
+    /// If attributes are passed, they are used for all arguments so the same
+    /// caveats apply as for `Performance.calculate`.
+    /// @throws Throws an error if the arguments are invalid
+    #[wasm_bindgen(js_name = calculateMany)]
+    pub fn calculate_many(
+        args: &JsMapOrAttributes,
+        scores: &JsPerformanceArgsList,
+    ) -> JsResult<Vec<JsPerformanceAttributes>> {
+        Self::calculate_many_with(args, scores, |mut perf| {
+            let state = perf.generate_state();
+
+            JsPerformanceAttributes::new(perf.calculate(), state)
+        })
+    }
+
+    /// Same as `Performance.calculateMany` but only returns the pp values.
+    /// @throws Throws an error if the arguments are invalid
+    #[wasm_bindgen(js_name = calculateManyPp)]
+    pub fn calculate_many_pp(
+        args: &JsMapOrAttributes,
+        scores: &JsPerformanceArgsList,
+    ) -> JsResult<Vec<f64>> {
+        Self::calculate_many_with(args, scores, |perf| perf.calculate().pp())
+    }
+
+    /// Calculate the performance of a score as well as the performance it
+    /// would have as full combo and the performance of full combos with the
+    /// given accuracies.
+    ///
+    /// Difficulty attributes are only calculated once for all scenarios.
+    /// Hitresults, combo, and accuracy of this calculator are ignored in
+    /// favor of the score state while all other arguments remain in effect.
+    ///
+    /// The same caveats regarding passed attributes apply as for
+    /// `Performance.calculate`.
+    /// @throws Throws an error if the arguments are invalid
+    #[wasm_bindgen(js_name = calculateScenarios)]
+    pub fn calculate_scenarios(
+        &self,
+        args: &JsMapOrAttributes,
+        state: &JsScoreState,
+        options: Option<JsPerformanceScenariosArgs>,
+    ) -> JsResult<JsPerformanceScenarios> {
+        let state = JsScoreState::deserialize(JsDeserializer::from_ref(state))?;
+
+        let options = options
+            .as_deref()
+            .map(util::from_value::<PerformanceScenariosArgs>)
+            .transpose()?
+            .unwrap_or_default();
+
+        let attrs = match MapOrAttrs::from_value(args)? {
+            MapOrAttrs::Map(map) => self.args.difficulty_args().calculate(&map),
+            MapOrAttrs::Attrs(attrs) => attrs,
         };
 
-        perf = self.args.apply(perf);
-        let state = perf.generate_state();
-        let attrs = JsPerformanceAttributes::new(perf.calculate(), state);
+        let calculate = |perf_args: &PerformanceArgs| {
+            let mut perf = perf_args.apply(Performance::new(attrs.clone()));
+            let state = perf.generate_state();
 
-        Ok(attrs)
+            JsPerformanceAttributes::new(perf.calculate(), state)
+        };
+
+        let mut perf_args = self.args.clone();
+        perf_args.set_state(&state);
+        let current = calculate(&perf_args);
+
+        // Leaving out the amount of 300s lets them fill the missed objects.
+        // Slider ticks and ends are left out too since missing them breaks
+        // combo. For osu!catch, droplets need to be filled as well.
+        perf_args.misses = Some(0);
+        perf_args.combo = Some(attrs.max_combo());
+        perf_args.n300 = None;
+        perf_args.large_tick_hits = None;
+        perf_args.small_tick_hits = None;
+        perf_args.slider_end_hits = None;
+
+        if let DifficultyAttributes::Catch(_) = attrs {
+            perf_args.n100 = None;
+        }
+
+        let fc = calculate(&perf_args);
+
+        perf_args.clear_state();
+
+        let accuracies: Vec<f64> = options
+            .accuracies
+            .iter()
+            .map(|&accuracy| {
+                perf_args.accuracy = Some(accuracy);
+
+                perf_args
+                    .apply(Performance::new(attrs.clone()))
+                    .calculate()
+                    .pp()
+            })
+            .collect();
+
+        let obj = js_sys::Object::new();
+        let obj_as_ext = obj.unchecked_ref::<util::ObjectExt>();
+
+        obj_as_ext.set(util::static_str_to_js("current"), current.into());
+        obj_as_ext.set(util::static_str_to_js("fc"), fc.into());
+        obj_as_ext.set(
+            util::static_str_to_js("accuracies"),
+            js_sys::Float64Array::from(accuracies.as_slice()).into(),
+        );
+
+        Ok(JsValue::from(obj).unchecked_into())
+    }
+
+    /// Search for the least demanding accuracy, amount of misses, or combo
+    /// that reaches the target pp.
+    ///
+    /// All other arguments of this calculator remain in effect. When varying
+    /// accuracy, specified hitresults other than misses are ignored.
+    ///
+    /// Difficulty attributes are only calculated once and the same caveats
+    /// regarding passed attributes apply as for `Performance.calculate`.
+    /// @throws Throws an error if the arguments are invalid or the target pp
+    /// is not a finite number
+    #[wasm_bindgen(js_name = solveForPp)]
+    pub fn solve_for_pp(
+        &self,
+        args: &JsMapOrAttributes,
+        target_pp: f64,
+        options: Option<JsSolveForPpArgs>,
+    ) -> JsResult<JsPpSolution> {
+        if !target_pp.is_finite() {
+            return Err(JsError::new("targetPp must be a finite number"));
+        }
+
+        let options = options
+            .as_deref()
+            .map(util::from_value::<SolveForPpArgs>)
+            .transpose()?
+            .unwrap_or_default();
+
+        let attrs = match MapOrAttrs::from_value(args)? {
+            MapOrAttrs::Map(map) => self.args.difficulty_args().calculate(&map),
+            MapOrAttrs::Attrs(attrs) => attrs,
+        };
+
+        let solution = solve::solve_for_pp(&self.args, &attrs, target_pp, options.vary);
+
+        Ok(solution.into())
+    }
+
+    /// Check whether the specified hitresults and combo are possible on a
+    /// beatmap.
+    ///
+    /// Only the values that were specified are validated. Errors and
+    /// warnings are determined through the object counts of the difficulty
+    /// attributes so the same caveats regarding passed attributes apply as
+    /// for `Performance.calculate`.
+    /// @throws Throws an error if the arguments are invalid
+    pub fn validate(&self, args: &JsMapOrAttributes) -> JsResult<JsScoreValidation> {
+        let attrs = match MapOrAttrs::from_value(args)? {
+            MapOrAttrs::Map(map) => self.args.difficulty_args().calculate(&map),
+            MapOrAttrs::Attrs(attrs) => attrs,
+        };
+
+        let fields = ScoreFields::from_args(&self.args);
+        let lazer = self.args.lazer.unwrap_or(true);
+
+        Ok(Validation::new(&fields, &attrs, lazer, &self.args.mods).into())
     }
 
     #[wasm_bindgen(setter)]
@@ -133,6 +400,13 @@ impl JsPerformance {
         self.args.hardrock_offsets = hardrock_offsets;
     }
 
+    #[wasm_bindgen(setter)]
+    pub fn set_cache(&mut self, cache: Option<JsDifficultyCacheRef>) -> JsResult<()> {
+        self.args.cache = JsDifficultyCache::from_ref(cache)?;
+
+        Ok(())
+    }
+
     #[wasm_bindgen(setter)]
     pub fn set_accuracy(&mut self, accuracy: Option<f64>) {
         self.args.accuracy = accuracy;
@@ -193,3 +467,82 @@ impl JsPerformance {
         self.args.hitresult_priority = hitresult_priority.map_or_else(Default::default, From::from);
     }
 }
+
+impl JsPerformance {
+    fn calculate_many_with<T>(
+        args: &JsMapOrAttributes,
+        scores: &JsPerformanceArgsList,
+        mut f: impl FnMut(Performance<'_>) -> T,
+    ) -> JsResult<Vec<T>> {
+        let perf_args_list = util::from_value::<Vec<PerformanceArgs>>(scores)?;
+
+        let map = match MapOrAttrs::from_value(args)? {
+            MapOrAttrs::Map(map) => map,
+            MapOrAttrs::Attrs(attrs) => {
+                let results = perf_args_list
+                    .iter()
+                    .map(|perf_args| f(perf_args.apply(Performance::new(attrs.clone()))))
+                    .collect();
+
+                return Ok(results);
+            }
+        };
+
+        let difficulty_args: Vec<_> = perf_args_list
+            .iter()
+            .map(PerformanceArgs::difficulty_args)
+            .collect();
+
+        let results = DifficultyArgs::calculate_many(&difficulty_args, &map)
+            .into_iter()
+            .zip(perf_args_list.iter())
+            .map(|(attrs, perf_args)| f(perf_args.apply(Performance::new(attrs))))
+            .collect();
+
+        Ok(results)
+    }
+
+    fn calculate_from_attrs(
+        perf_args: &PerformanceArgs,
+        attrs: DifficultyAttributes,
+    ) -> JsPerformanceAttributes {
+        let mut perf = perf_args.apply(Performance::new(attrs));
+        let state = perf.generate_state();
+
+        JsPerformanceAttributes::new(perf.calculate(), state)
+    }
+
+    fn calculate_with(
+        perf_args: &PerformanceArgs,
+        args: &JsMapOrAttributes,
+        mode: Option<GameMode>,
+    ) -> JsResult<JsPerformanceAttributes> {
+        let map_or_attrs = MapOrAttrs::from_value(args)?;
+        let map;
+
+        let mut perf = match map_or_attrs {
+            // Cached attributes can only be used if no conversion is required
+            MapOrAttrs::Map(map_)
+                if perf_args.cache.is_some() && mode.is_none_or(|mode| mode == map_.inner.mode) =>
+            {
+                Performance::new(perf_args.difficulty_args().calculate(&map_))
+            }
+            MapOrAttrs::Map(map_) => {
+                map = map_;
+
+                Performance::new(&map.inner)
+            }
+            MapOrAttrs::Attrs(attrs) => Performance::new(attrs),
+        };
+
+        if let Some(mode) = mode {
+            perf = perf.mode_or_ignore(mode);
+        }
+
+        perf = perf_args.apply(perf);
+        let state = perf.generate_state();
+        let attrs = JsPerformanceAttributes::new(perf.calculate(), state);
+
+        Ok(attrs)
+    }
+}
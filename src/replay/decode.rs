@@ -0,0 +1,544 @@
+use std::{error, fmt, io, str};
+
+use rosu_mods::{GameMods, GameModsIntermode, serde::GameModsSeed};
+use rosu_pp::model::mode::GameMode;
+use serde::de::DeserializeSeed;
+
+/// The first replay version that may contain lazer score info.
+const LAZER_VERSION: i32 = 30000001;
+
+/// Bitflag of the legacy `TargetPractice` mod.
+const TARGET_PRACTICE: u32 = 1 << 23;
+
+/// Frame time that marks the frame containing the RNG seed.
+const SEED_FRAME_TIME: i64 = -12345;
+
+/// Upper bound for the size of decompressed replay data.
+///
+/// Even hour-long replays stay well below this so anything larger is assumed
+/// to be malicious.
+const MAX_DECOMPRESSED_LEN: usize = 64 * 1024 * 1024;
+
+/// All data of a decoded `.osr` file.
+pub struct Replay {
+    pub mode: GameMode,
+    pub version: i32,
+    pub beatmap_hash: String,
+    pub player_name: String,
+    pub replay_hash: String,
+    pub n300: u16,
+    pub n100: u16,
+    pub n50: u16,
+    pub n_geki: u16,
+    pub n_katu: u16,
+    pub misses: u16,
+    pub score: i32,
+    pub max_combo: u16,
+    pub perfect: bool,
+    pub mods_bits: u32,
+    pub life_bar: String,
+    pub timestamp: i64,
+    pub online_id: i64,
+    pub target_practice_accuracy: Option<f64>,
+    pub frames: Vec<ReplayFrame>,
+    pub rng_seed: Option<i32>,
+    pub lazer: Option<LazerScoreInfo>,
+}
+
+/// A single replay frame with its absolute time.
+#[derive(Copy, Clone)]
+pub struct ReplayFrame {
+    pub time: f64,
+    pub x: f32,
+    pub y: f32,
+    pub keys: u32,
+}
+
+/// Additional score data appended to replays exported by osu!lazer.
+#[derive(Default, serde::Deserialize)]
+#[serde(default)]
+pub struct LazerScoreInfo {
+    pub mods: Vec<serde_json::Value>,
+    pub statistics: LazerStatistics,
+}
+
+/// Hitresult counts of a lazer score that are not stored in the replay header.
+#[derive(Default, serde::Deserialize)]
+#[serde(default)]
+pub struct LazerStatistics {
+    pub large_tick_hit: u32,
+    pub small_tick_hit: u32,
+    pub slider_tail_hit: u32,
+}
+
+impl Replay {
+    /// Decode a replay from the content of an `.osr` file.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ParseReplayError> {
+        let mut reader = Reader::new(bytes);
+
+        let mode = match reader.u8()? {
+            0 => GameMode::Osu,
+            1 => GameMode::Taiko,
+            2 => GameMode::Catch,
+            3 => GameMode::Mania,
+            mode => return Err(ParseReplayError::Mode(mode)),
+        };
+
+        let version = reader.i32()?;
+        let beatmap_hash = reader.string()?;
+        let player_name = reader.string()?;
+        let replay_hash = reader.string()?;
+        let n300 = reader.u16()?;
+        let n100 = reader.u16()?;
+        let n50 = reader.u16()?;
+        let n_geki = reader.u16()?;
+        let n_katu = reader.u16()?;
+        let misses = reader.u16()?;
+        let score = reader.i32()?;
+        let max_combo = reader.u16()?;
+        let perfect = reader.u8()? != 0;
+        let mods_bits = reader.i32()? as u32;
+        let life_bar = reader.string()?;
+        let timestamp = reader.i64()?;
+
+        let compressed_len = reader.i32()?;
+        let compressed = reader.bytes(compressed_len.max(0) as usize)?;
+        let (frames, rng_seed) = parse_frames(&decompress(compressed, MAX_DECOMPRESSED_LEN)?)?;
+
+        let online_id = reader.i64()?;
+
+        let target_practice_accuracy = if mods_bits & TARGET_PRACTICE > 0 {
+            Some(reader.f64()?)
+        } else {
+            None
+        };
+
+        let lazer = if version >= LAZER_VERSION && !reader.is_empty() {
+            let len = reader.i32()?;
+            let compressed = reader.bytes(len.max(0) as usize)?;
+            let json = decompress(compressed, MAX_DECOMPRESSED_LEN)?;
+
+            Some(serde_json::from_slice(&json).map_err(ParseReplayError::LazerScoreInfo)?)
+        } else {
+            None
+        };
+
+        Ok(Self {
+            mode,
+            version,
+            beatmap_hash,
+            player_name,
+            replay_hash,
+            n300,
+            n100,
+            n50,
+            n_geki,
+            n_katu,
+            misses,
+            score,
+            max_combo,
+            perfect,
+            mods_bits,
+            life_bar,
+            timestamp,
+            online_id,
+            target_practice_accuracy,
+            frames,
+            rng_seed,
+            lazer,
+        })
+    }
+
+    /// The replay's mods.
+    ///
+    /// Prefers the lazer mods including their settings if available and falls
+    /// back to the legacy bitflags otherwise.
+    pub fn mods(&self) -> GameMods {
+        let mode = match self.mode {
+            GameMode::Osu => rosu_mods::GameMode::Osu,
+            GameMode::Taiko => rosu_mods::GameMode::Taiko,
+            GameMode::Catch => rosu_mods::GameMode::Catch,
+            GameMode::Mania => rosu_mods::GameMode::Mania,
+        };
+
+        let seed = GameModsSeed::Mode {
+            mode,
+            deny_unknown_fields: false,
+        };
+
+        self.lazer
+            .as_ref()
+            .filter(|info| !info.mods.is_empty())
+            .and_then(|info| {
+                let value = serde_json::Value::Array(info.mods.clone());
+
+                seed.deserialize(value).ok()
+            })
+            .unwrap_or_else(|| GameModsIntermode::from_bits(self.mods_bits).with_mode(mode))
+    }
+}
+
+fn decompress(bytes: &[u8], limit: usize) -> Result<Vec<u8>, ParseReplayError> {
+    let mut output = LimitedWriter {
+        buf: Vec::new(),
+        limit,
+        exceeded: false,
+    };
+
+    if !bytes.is_empty() {
+        let options = lzma_rs::decompress::Options {
+            memlimit: Some(limit),
+            ..Default::default()
+        };
+
+        let res = lzma_rs::lzma_decompress_with_options(
+            &mut io::BufReader::new(bytes),
+            &mut output,
+            &options,
+        );
+
+        match res {
+            Ok(()) => {}
+            // The dictionary never holds more than the decompressed data so
+            // exceeding its memory limit also means exceeding the size limit
+            Err(_) if output.exceeded => return Err(ParseReplayError::TooLarge),
+            Err(lzma_rs::error::Error::LzmaError(msg))
+                if msg.starts_with("exceeded memory limit") =>
+            {
+                return Err(ParseReplayError::TooLarge);
+            }
+            Err(err) => return Err(ParseReplayError::Lzma(err)),
+        }
+    }
+
+    Ok(output.buf)
+}
+
+/// Writer that fails once more than `limit` bytes are written.
+struct LimitedWriter {
+    buf: Vec<u8>,
+    limit: usize,
+    exceeded: bool,
+}
+
+impl io::Write for LimitedWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.buf.len() + buf.len() > self.limit {
+            self.exceeded = true;
+
+            return Err(io::Error::other("decompressed data exceeds the limit"));
+        }
+
+        self.buf.extend_from_slice(buf);
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+fn parse_frames(bytes: &[u8]) -> Result<(Vec<ReplayFrame>, Option<i32>), ParseReplayError> {
+    let content = str::from_utf8(bytes).map_err(|_| ParseReplayError::Frame)?;
+
+    let mut frames = Vec::new();
+    let mut rng_seed = None;
+    let mut time = 0_i64;
+
+    for frame in content.split(',').filter(|frame| !frame.is_empty()) {
+        let mut split = frame.split('|');
+
+        let (Some(delta), Some(x), Some(y), Some(keys)) =
+            (split.next(), split.next(), split.next(), split.next())
+        else {
+            return Err(ParseReplayError::Frame);
+        };
+
+        let delta = delta.parse::<i64>().map_err(|_| ParseReplayError::Frame)?;
+        let keys = keys.parse::<i64>().map_err(|_| ParseReplayError::Frame)?;
+
+        if delta == SEED_FRAME_TIME {
+            rng_seed = Some(keys as i32);
+
+            continue;
+        }
+
+        time = time.checked_add(delta).ok_or(ParseReplayError::Frame)?;
+
+        frames.push(ReplayFrame {
+            time: time as f64,
+            x: x.parse().map_err(|_| ParseReplayError::Frame)?,
+            y: y.parse().map_err(|_| ParseReplayError::Frame)?,
+            keys: keys as u32,
+        });
+    }
+
+    Ok((frames, rng_seed))
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    const fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+
+    const fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8], ParseReplayError> {
+        if self.bytes.len() < len {
+            return Err(ParseReplayError::UnexpectedEof);
+        }
+
+        let (bytes, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+
+        Ok(bytes)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], ParseReplayError> {
+        self.bytes(N).map(|bytes| bytes.try_into().unwrap())
+    }
+
+    fn u8(&mut self) -> Result<u8, ParseReplayError> {
+        self.array().map(|[byte]| byte)
+    }
+
+    fn u16(&mut self) -> Result<u16, ParseReplayError> {
+        self.array().map(u16::from_le_bytes)
+    }
+
+    fn i32(&mut self) -> Result<i32, ParseReplayError> {
+        self.array().map(i32::from_le_bytes)
+    }
+
+    fn i64(&mut self) -> Result<i64, ParseReplayError> {
+        self.array().map(i64::from_le_bytes)
+    }
+
+    fn f64(&mut self) -> Result<f64, ParseReplayError> {
+        self.array().map(f64::from_le_bytes)
+    }
+
+    fn uleb128(&mut self) -> Result<usize, ParseReplayError> {
+        let mut value = 0;
+        let mut shift = 0;
+
+        loop {
+            let byte = self.u8()?;
+            value |= usize::from(byte & 0x7F) << shift;
+
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+
+            shift += 7;
+
+            if shift >= usize::BITS {
+                return Err(ParseReplayError::String);
+            }
+        }
+    }
+
+    fn string(&mut self) -> Result<String, ParseReplayError> {
+        match self.u8()? {
+            0x00 => Ok(String::new()),
+            0x0B => {
+                let len = self.uleb128()?;
+                let bytes = self.bytes(len)?;
+
+                String::from_utf8(bytes.to_vec()).map_err(|_| ParseReplayError::String)
+            }
+            _ => Err(ParseReplayError::String),
+        }
+    }
+}
+
+/// All the ways that decoding an `.osr` file can fail.
+#[derive(Debug)]
+pub enum ParseReplayError {
+    Frame,
+    LazerScoreInfo(serde_json::Error),
+    Lzma(lzma_rs::error::Error),
+    Mode(u8),
+    String,
+    TooLarge,
+    UnexpectedEof,
+}
+
+impl error::Error for ParseReplayError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::LazerScoreInfo(err) => Some(err),
+            Self::Lzma(err) => Some(err),
+            Self::Frame | Self::Mode(_) | Self::String | Self::TooLarge | Self::UnexpectedEof => {
+                None
+            }
+        }
+    }
+}
+
+impl fmt::Display for ParseReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Frame => f.write_str("invalid replay frame"),
+            Self::LazerScoreInfo(_) => f.write_str("failed to parse lazer score info"),
+            Self::Lzma(_) => f.write_str("failed to decompress LZMA data"),
+            Self::Mode(mode) => write!(f, "invalid mode {mode}"),
+            Self::String => f.write_str("invalid string"),
+            Self::TooLarge => f.write_str("decompressed data is too large"),
+            Self::UnexpectedEof => f.write_str("unexpected end of file"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LAZER_REPLAY: &[u8] = include_bytes!("../../resources/lazer.osr");
+
+    #[test]
+    fn decode_lazer_replay() {
+        let replay = Replay::from_bytes(LAZER_REPLAY).unwrap();
+
+        assert_eq!(replay.mode, GameMode::Osu);
+        assert_eq!(replay.version, 30000016);
+        assert_eq!(replay.beatmap_hash, "0123456789abcdef0123456789abcdef");
+        assert_eq!(replay.player_name, "tester");
+        assert_eq!(
+            (replay.n300, replay.n100, replay.n50, replay.misses),
+            (3, 1, 0, 1)
+        );
+        assert_eq!((replay.n_geki, replay.n_katu), (1, 0));
+        assert_eq!(replay.score, 123456);
+        assert_eq!(replay.max_combo, 5);
+        assert!(!replay.perfect);
+        assert_eq!(replay.mods_bits, 72);
+        assert_eq!(replay.life_bar, "0|1,500|0.9,");
+        assert_eq!(replay.online_id, 4242);
+        assert_eq!(replay.target_practice_accuracy, None);
+        assert_eq!(replay.rng_seed, Some(777));
+
+        let frames: Vec<_> = replay
+            .frames
+            .iter()
+            .map(|frame| (frame.time, frame.x, frame.y, frame.keys))
+            .collect();
+
+        assert_eq!(
+            frames,
+            [
+                (0.0, 256.0, -500.0, 0),
+                (-1.0, 256.0, -500.0, 0),
+                (9.0, 100.5, 200.0, 1),
+                (25.0, 120.0, 210.25, 5),
+            ]
+        );
+
+        let lazer = replay.lazer.as_ref().unwrap();
+        assert_eq!(lazer.statistics.large_tick_hit, 2);
+        assert_eq!(lazer.statistics.small_tick_hit, 0);
+        assert_eq!(lazer.statistics.slider_tail_hit, 1);
+
+        let mods = replay.mods();
+        assert_eq!(mods.bits(), 72);
+        assert_eq!(mods.clock_rate(), Some(1.3));
+    }
+
+    #[test]
+    fn truncated_replay() {
+        // Cutting off the entire lazer score info yields a valid stable replay
+        let without_lazer = LAZER_REPLAY.len() - 4 - lazer_info_len();
+
+        for len in 0..LAZER_REPLAY.len() {
+            match Replay::from_bytes(&LAZER_REPLAY[..len]) {
+                Ok(replay) => {
+                    assert_eq!(len, without_lazer);
+                    assert!(replay.lazer.is_none());
+                }
+                Err(_) => assert_ne!(len, without_lazer),
+            }
+        }
+    }
+
+    #[test]
+    fn corrupted_replay() {
+        for i in 0..LAZER_REPLAY.len() {
+            for mask in [0x01, 0x80, 0xFF] {
+                let mut bytes = LAZER_REPLAY.to_vec();
+                bytes[i] ^= mask;
+
+                // Must not panic
+                let _ = Replay::from_bytes(&bytes);
+            }
+        }
+    }
+
+    #[test]
+    fn garbage_replay() {
+        let mut state = 0x2545_F491_u32;
+
+        for len in 0..512 {
+            let bytes: Vec<u8> = (0..len)
+                .map(|_| {
+                    state ^= state << 13;
+                    state ^= state >> 17;
+                    state ^= state << 5;
+
+                    state as u8
+                })
+                .collect();
+
+            assert!(Replay::from_bytes(&bytes).is_err());
+        }
+    }
+
+    #[test]
+    fn overflowing_frame_times() {
+        let frames = format!("{max}|0|0|0,{max}|0|0|0", max = i64::MAX);
+
+        assert!(matches!(
+            parse_frames(frames.as_bytes()),
+            Err(ParseReplayError::Frame)
+        ));
+    }
+
+    #[test]
+    fn decompression_limit() {
+        let data = vec![0; 1 << 16];
+        let mut compressed = Vec::new();
+        lzma_rs::lzma_compress(&mut data.as_slice(), &mut compressed).unwrap();
+
+        assert!(matches!(
+            decompress(&compressed, data.len() - 1),
+            Err(ParseReplayError::TooLarge)
+        ));
+        assert_eq!(decompress(&compressed, data.len()).unwrap(), data);
+    }
+
+    /// The length of the compressed lazer score info at the end of the
+    /// fixture.
+    fn lazer_info_len() -> usize {
+        let mut reader = Reader::new(LAZER_REPLAY);
+        reader.bytes(1 + 4).unwrap();
+
+        for _ in 0..3 {
+            reader.string().unwrap();
+        }
+
+        reader.bytes(6 * 2 + 4 + 2 + 1 + 4).unwrap();
+        reader.string().unwrap();
+        reader.bytes(8).unwrap();
+
+        let frames_len = reader.i32().unwrap() as usize;
+        reader.bytes(frames_len + 8).unwrap();
+
+        reader.i32().unwrap() as usize
+    }
+}
@@ -0,0 +1,242 @@
+use std::{error, fmt::Write};
+
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::{
+    JsError, JsResult,
+    args::{beatmap::BeatmapContent, performance::PerformanceArgs},
+    mode::JsGameMode,
+    util,
+};
+
+pub use self::decode::Replay;
+
+mod decode;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = ReplayContent)]
+    pub type JsReplayContent;
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const _: &'static str = r#"/**
+* The content of a `.osr` file as bytes.
+*/
+export type ReplayContent = Uint8Array;"#;
+
+/// A decoded `.osr` replay file.
+///
+/// It is recommended to call the method `Replay.free` on instances that are
+/// no longer in use to avoid the risk of leaking memory.
+#[wasm_bindgen(js_name = Replay)]
+pub struct JsReplay {
+    pub(crate) inner: Replay,
+}
+
+#[wasm_bindgen(js_class = Replay)]
+impl JsReplay {
+    /// Create a new replay instance by decoding an `.osr` file's content.
+    /// @throws Throws an error if decoding the replay failed
+    #[wasm_bindgen(constructor)]
+    pub fn new(args: &JsReplayContent) -> JsResult<JsReplay> {
+        let content = util::from_value::<BeatmapContent>(args)?;
+
+        match Replay::from_bytes(&content.bytes) {
+            Ok(inner) => Ok(Self { inner }),
+            Err(err) => {
+                let mut e = &err as &dyn error::Error;
+                let mut content = format!("Failed to decode replay: {e}");
+
+                while let Some(src) = e.source() {
+                    let _ = writeln!(content, "  - caused by: {src}");
+                    e = src;
+                }
+
+                Err(JsError::new(&content))
+            }
+        }
+    }
+
+    /// MD5 hash of the played beatmap.
+    #[wasm_bindgen(js_name = beatmapHash, getter)]
+    pub fn beatmap_hash(&self) -> String {
+        self.inner.beatmap_hash.clone()
+    }
+
+    #[wasm_bindgen(js_name = playerName, getter)]
+    pub fn player_name(&self) -> String {
+        self.inner.player_name.clone()
+    }
+
+    /// MD5 hash of the replay.
+    #[wasm_bindgen(js_name = replayHash, getter)]
+    pub fn replay_hash(&self) -> String {
+        self.inner.replay_hash.clone()
+    }
+
+    /// The life bar graph as comma-separated `time|life` pairs.
+    #[wasm_bindgen(js_name = lifeBar, getter)]
+    pub fn life_bar(&self) -> String {
+        self.inner.life_bar.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn mode(&self) -> JsGameMode {
+        JsGameMode::from(self.inner.mode)
+    }
+
+    /// The legacy bitflags of the replay's mods.
+    #[wasm_bindgen(getter)]
+    pub fn mods(&self) -> u32 {
+        self.inner.mods_bits
+    }
+
+    /// Whether the replay contains additional score info from osu!lazer.
+    #[wasm_bindgen(js_name = isLazer, getter)]
+    pub fn is_lazer(&self) -> bool {
+        self.inner.lazer.is_some()
+    }
+
+    /// Windows ticks i.e. 100ns intervals since 0001-01-01 when the score was
+    /// set.
+    #[wasm_bindgen(getter)]
+    pub fn timestamp(&self) -> f64 {
+        self.inner.timestamp as f64
+    }
+
+    #[wasm_bindgen(js_name = onlineId, getter)]
+    pub fn online_id(&self) -> f64 {
+        self.inner.online_id as f64
+    }
+
+    /// The accuracy of a score with the `TargetPractice` mod.
+    #[wasm_bindgen(js_name = targetPracticeAccuracy, getter)]
+    pub fn target_practice_accuracy(&self) -> Option<f64> {
+        self.inner.target_practice_accuracy
+    }
+
+    /// The seed for osu!'s random number generator.
+    #[wasm_bindgen(js_name = rngSeed, getter)]
+    pub fn rng_seed(&self) -> Option<i32> {
+        self.inner.rng_seed
+    }
+
+    /// The amount of "large tick" hits.
+    ///
+    /// Only available for osu!lazer replays.
+    #[wasm_bindgen(js_name = largeTickHits, getter)]
+    pub fn large_tick_hits(&self) -> Option<u32> {
+        self.inner
+            .lazer
+            .as_ref()
+            .map(|info| info.statistics.large_tick_hit)
+    }
+
+    /// The amount of "small tick" hits.
+    ///
+    /// Only available for osu!lazer replays.
+    #[wasm_bindgen(js_name = smallTickHits, getter)]
+    pub fn small_tick_hits(&self) -> Option<u32> {
+        self.inner
+            .lazer
+            .as_ref()
+            .map(|info| info.statistics.small_tick_hit)
+    }
+
+    /// The amount of slider end hits.
+    ///
+    /// Only available for osu!lazer replays.
+    #[wasm_bindgen(js_name = sliderEndHits, getter)]
+    pub fn slider_end_hits(&self) -> Option<u32> {
+        self.inner
+            .lazer
+            .as_ref()
+            .map(|info| info.statistics.slider_tail_hit)
+    }
+
+    /// The amount of replay frames.
+    #[wasm_bindgen(js_name = nFrames, getter)]
+    pub fn n_frames(&self) -> usize {
+        self.inner.frames.len()
+    }
+
+    /// The absolute time in milliseconds of each replay frame.
+    #[wasm_bindgen(js_name = frameTimes)]
+    pub fn frame_times(&self) -> Vec<f64> {
+        self.inner.frames.iter().map(|frame| frame.time).collect()
+    }
+
+    /// The x-coordinate of each replay frame.
+    #[wasm_bindgen(js_name = frameX)]
+    pub fn frame_x(&self) -> Vec<f32> {
+        self.inner.frames.iter().map(|frame| frame.x).collect()
+    }
+
+    /// The y-coordinate of each replay frame.
+    #[wasm_bindgen(js_name = frameY)]
+    pub fn frame_y(&self) -> Vec<f32> {
+        self.inner.frames.iter().map(|frame| frame.y).collect()
+    }
+
+    /// The pressed keys of each replay frame as bitflags.
+    #[wasm_bindgen(js_name = frameKeys)]
+    pub fn frame_keys(&self) -> Vec<u32> {
+        self.inner.frames.iter().map(|frame| frame.keys).collect()
+    }
+}
+
+impl JsReplay {
+    /// Overwrite the score-specific arguments with the replay's data.
+    pub fn apply(&self, args: &mut PerformanceArgs) {
+        let replay = &self.inner;
+
+        args.mods = replay.mods();
+        args.lazer = Some(replay.lazer.is_some());
+        args.accuracy = None;
+        args.combo = Some(u32::from(replay.max_combo));
+        args.n_geki = Some(u32::from(replay.n_geki));
+        args.n_katu = Some(u32::from(replay.n_katu));
+        args.n300 = Some(u32::from(replay.n300));
+        args.n100 = Some(u32::from(replay.n100));
+        args.n50 = Some(u32::from(replay.n50));
+        args.misses = Some(u32::from(replay.misses));
+
+        if let Some(ref info) = replay.lazer {
+            args.large_tick_hits = Some(info.statistics.large_tick_hit);
+            args.small_tick_hits = Some(info.statistics.small_tick_hit);
+            args.slider_end_hits = Some(info.statistics.slider_tail_hit);
+        } else {
+            args.large_tick_hits = None;
+            args.small_tick_hits = None;
+            args.slider_end_hits = None;
+        }
+    }
+}
+
+macro_rules! replay_getters {
+    ( $( $field:ident as $getter:ident: $ty:ty, )+ ) => {
+        #[wasm_bindgen(js_class = Replay)]
+        impl JsReplay {
+            $(
+                #[wasm_bindgen(js_name = $getter, getter)]
+                pub fn $field(&self) -> $ty {
+                    self.inner.$field
+                }
+            )*
+        }
+    };
+}
+
+replay_getters! {
+    version as version: i32,
+    n300 as n300: u16,
+    n100 as n100: u16,
+    n50 as n50: u16,
+    n_geki as nGeki: u16,
+    n_katu as nKatu: u16,
+    misses as misses: u16,
+    score as score: i32,
+    max_combo as maxCombo: u16,
+    perfect as perfect: bool,
+}
@@ -1,16 +1,41 @@
 use std::fmt;
 
-use rosu_pp::any::ScoreState;
+use rosu_mods::{GameMod, GameModIntermode, GameMods};
+use rosu_pp::{
+    Difficulty, Performance,
+    any::{DifficultyAttributes, ScoreState},
+    catch::CatchScoreState,
+    mania::ManiaScoreState,
+    model::mode::GameMode,
+    osu::{OsuScoreOrigin, OsuScoreState},
+    taiko::TaikoScoreState,
+};
 use serde::de;
 use wasm_bindgen::prelude::*;
 
-use crate::util;
+use crate::{
+    JsResult,
+    args::{
+        performance::{JsMapOrAttributes, MapOrAttrs},
+        score_state::{
+            JsStateAccuracyArgs, JsStateFromAccuracyArgs, JsStateValidationArgs, StateAccuracyArgs,
+            StateFromAccuracyArgs, StateValidationArgs,
+        },
+    },
+    deserializer::JsDeserializer,
+    mode::JsGameMode,
+    util,
+    validation::{JsScoreValidation, ScoreFields, Validation},
+};
 
 #[wasm_bindgen]
 extern "C" {
     #[wasm_bindgen(typescript_type = ScoreState)]
     #[derive(Clone)]
     pub type JsScoreState;
+
+    #[wasm_bindgen(typescript_type = StateFromAccuracy)]
+    pub type JsStateFromAccuracy;
 }
 
 #[wasm_bindgen(typescript_custom_section)]
@@ -83,8 +108,384 @@ export interface ScoreState {
     * Amount of current misses (fruits + droplets for osu!catch).
     */
     misses?: number;
+}
+
+/**
+* The result of `ScoreStateUtils.fromAccuracy`.
+*/
+export interface StateFromAccuracy {
+    /**
+    * The generated score state.
+    */
+    state: ScoreState;
+    /**
+    * The accuracy between `0.0` and `100.0` that the generated score state
+    * achieves.
+    */
+    accuracy: number;
 }"#;
 
+/// Functions that operate on a `ScoreState`.
+///
+/// Since `ScoreState` is a plain object rather than a class, its functions
+/// are provided through this class.
+#[wasm_bindgen(js_name = ScoreStateUtils)]
+pub struct JsScoreStateUtils;
+
+#[wasm_bindgen(js_class = ScoreStateUtils)]
+impl JsScoreStateUtils {
+    /// Generate the score state that best matches the given accuracy without
+    /// calculating performance.
+    ///
+    /// If a beatmap is passed as argument, difficulty attributes will have to
+    /// be calculated internally which is a comparably expensive task. Hence,
+    /// passing previously calculated attributes should be prefered whenever
+    /// available.
+    /// @throws Throws an error if the arguments are invalid
+    #[wasm_bindgen(js_name = fromAccuracy)]
+    pub fn from_accuracy(
+        args: &JsMapOrAttributes,
+        options: Option<JsStateFromAccuracyArgs>,
+    ) -> JsResult<JsStateFromAccuracy> {
+        let map_or_attrs = MapOrAttrs::from_value(args)?;
+
+        let args = options
+            .as_deref()
+            .map(util::from_value::<StateFromAccuracyArgs>)
+            .transpose()?
+            .unwrap_or_default();
+
+        let lazer = args.lazer.unwrap_or(true);
+
+        let attrs = match map_or_attrs {
+            MapOrAttrs::Map(map) => Difficulty::new()
+                .mods(args.mods.clone())
+                .lazer(lazer)
+                .calculate(&map.inner),
+            MapOrAttrs::Attrs(attrs) => attrs,
+        };
+
+        let mut perf = Performance::new(attrs.clone())
+            .mods(args.mods.clone())
+            .lazer(lazer)
+            .hitresult_priority(args.priority);
+
+        if let Some(accuracy) = args.accuracy {
+            perf = perf.accuracy(accuracy);
+        }
+
+        if let Some(misses) = args.misses {
+            perf = perf.misses(misses);
+        }
+
+        if let Some(combo) = args.combo {
+            perf = perf.combo(combo);
+        }
+
+        let state = perf.generate_state();
+        let mode = match attrs {
+            DifficultyAttributes::Osu(_) => GameMode::Osu,
+            DifficultyAttributes::Taiko(_) => GameMode::Taiko,
+            DifficultyAttributes::Catch(_) => GameMode::Catch,
+            DifficultyAttributes::Mania(_) => GameMode::Mania,
+        };
+        let slider_counts = SliderCounts::from_attrs(&attrs);
+        let accuracy = 100.0 * accuracy(&state, mode, lazer, &args.mods, slider_counts);
+
+        let obj = js_sys::Object::new();
+        let obj_as_ext = obj.unchecked_ref::<util::ObjectExt>();
+        obj_as_ext.set(
+            util::static_str_to_js("state"),
+            JsScoreState::from(state).into(),
+        );
+        obj_as_ext.set(util::static_str_to_js("accuracy"), accuracy.into());
+
+        Ok(JsValue::from(obj).into())
+    }
+
+    /// Calculate the accuracy between `0.0` and `100.0` of a score state,
+    /// defined the same way as in performance calculation.
+    ///
+    /// For osu!lazer scores in osu!, the maximum amount of slider-related
+    /// hitresults is taken from the given `attributes`. If none are given,
+    /// the state's own slider-related hits are considered to be the maximum.
+    /// @throws Throws an error if the arguments are invalid
+    pub fn accuracy(
+        state: &JsScoreState,
+        mode: JsGameMode,
+        options: Option<JsStateAccuracyArgs>,
+    ) -> JsResult<f64> {
+        let (state, args) = Self::deserialize_state_args(state, options)?;
+        let slider_counts = args.slider_counts();
+        let lazer = args.lazer.unwrap_or(true);
+
+        Ok(100.0 * accuracy(&state, mode.into(), lazer, &args.mods, slider_counts))
+    }
+
+    /// Determine the grade that a score state would be awarded.
+    ///
+    /// osu!stable and osu!lazer grades differ; the former depend on the
+    /// ratio of hitresults in osu! and osu!taiko while the latter depend on
+    /// accuracy. The `HD` and `FL` mods, as well as `FI` in osu!mania, turn
+    /// `SS` and `S` grades into their silver variants.
+    ///
+    /// See `ScoreStateUtils.accuracy` regarding the `attributes` argument.
+    /// @throws Throws an error if the arguments are invalid
+    pub fn grade(
+        state: &JsScoreState,
+        mode: JsGameMode,
+        options: Option<JsStateAccuracyArgs>,
+    ) -> JsResult<JsGrade> {
+        let (state, args) = Self::deserialize_state_args(state, options)?;
+        let slider_counts = args.slider_counts();
+        let lazer = args.lazer.unwrap_or(true);
+
+        Ok(grade(&state, mode.into(), lazer, &args.mods, slider_counts))
+    }
+
+    /// Check whether the hitresults and combo of a score state are possible
+    /// on a beatmap.
+    ///
+    /// Errors and warnings are determined through the object counts of the
+    /// difficulty attributes. If a beatmap is passed as argument, difficulty
+    /// attributes will have to be calculated internally which is a
+    /// comparably expensive task.
+    /// @throws Throws an error if the arguments are invalid
+    pub fn validate(
+        args: &JsMapOrAttributes,
+        state: &JsScoreState,
+        options: Option<JsStateValidationArgs>,
+    ) -> JsResult<JsScoreValidation> {
+        let map_or_attrs = MapOrAttrs::from_value(args)?;
+        let state = JsScoreState::deserialize(JsDeserializer::from_ref(state))?;
+
+        let args = options
+            .as_deref()
+            .map(util::from_value::<StateValidationArgs>)
+            .transpose()?
+            .unwrap_or_default();
+
+        let lazer = args.lazer.unwrap_or(true);
+
+        let attrs = match map_or_attrs {
+            MapOrAttrs::Map(map) => Difficulty::new()
+                .mods(args.mods.clone())
+                .lazer(lazer)
+                .calculate(&map.inner),
+            MapOrAttrs::Attrs(attrs) => attrs,
+        };
+
+        let fields = ScoreFields::from_state(&state);
+
+        Ok(Validation::new(&fields, &attrs, lazer, &args.mods).into())
+    }
+}
+
+impl JsScoreStateUtils {
+    fn deserialize_state_args(
+        state: &JsScoreState,
+        options: Option<JsStateAccuracyArgs>,
+    ) -> JsResult<(ScoreState, StateAccuracyArgs)> {
+        let state = JsScoreState::deserialize(JsDeserializer::from_ref(state))?;
+
+        let args = options
+            .as_deref()
+            .map(util::from_value::<StateAccuracyArgs>)
+            .transpose()?
+            .unwrap_or_default();
+
+        Ok((state, args))
+    }
+}
+
+/// The grade of a score.
+#[wasm_bindgen(js_name = Grade)]
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum JsGrade {
+    /// Silver SS, i.e. SS with `HD` or `FL`
+    SSH,
+    /// SS, i.e. 100% accuracy
+    SS,
+    /// Silver S, i.e. S with `HD` or `FL`
+    SH,
+    S,
+    A,
+    B,
+    C,
+    D,
+}
+
+/// The amount of osu! sliders and large ticks which determine the maximum of
+/// slider-related hitresults in osu!lazer.
+#[derive(Copy, Clone)]
+pub struct SliderCounts {
+    pub n_sliders: u32,
+    pub n_large_ticks: u32,
+}
+
+impl SliderCounts {
+    pub fn from_attrs(attrs: &DifficultyAttributes) -> Option<Self> {
+        match attrs {
+            DifficultyAttributes::Osu(attrs) => Some(Self {
+                n_sliders: attrs.n_sliders,
+                n_large_ticks: attrs.n_large_ticks,
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// The accuracy between `0.0` and `1.0` of a [`ScoreState`], defined the
+/// same way as in rosu-pp's performance calculation.
+///
+/// If slider counts are not available for an osu!lazer score in osu!, the
+/// state's slider-related hits are considered to be the maximum.
+pub fn accuracy(
+    state: &ScoreState,
+    mode: GameMode,
+    lazer: bool,
+    mods: &GameMods,
+    slider_counts: Option<SliderCounts>,
+) -> f64 {
+    match mode {
+        GameMode::Osu => {
+            let origin = match (lazer, no_slider_head_acc(mods, lazer), slider_counts) {
+                (false, ..) => OsuScoreOrigin::Stable,
+                (true, false, Some(counts)) => OsuScoreOrigin::WithSliderAcc {
+                    max_large_ticks: counts.n_large_ticks,
+                    max_slider_ends: counts.n_sliders,
+                },
+                (true, false, None) => OsuScoreOrigin::WithSliderAcc {
+                    max_large_ticks: state.osu_large_tick_hits,
+                    max_slider_ends: state.slider_end_hits,
+                },
+                (true, true, Some(counts)) => OsuScoreOrigin::WithoutSliderAcc {
+                    max_large_ticks: counts.n_sliders + counts.n_large_ticks,
+                    max_small_ticks: counts.n_sliders,
+                },
+                (true, true, None) => OsuScoreOrigin::WithoutSliderAcc {
+                    max_large_ticks: state.osu_large_tick_hits,
+                    max_small_ticks: state.osu_small_tick_hits,
+                },
+            };
+
+            OsuScoreState::from(state.clone()).accuracy(origin)
+        }
+        GameMode::Taiko => TaikoScoreState::from(state.clone()).accuracy(),
+        GameMode::Catch => CatchScoreState::from(state.clone()).accuracy(),
+        GameMode::Mania => {
+            let classic = !lazer || mods.contains_intermode(GameModIntermode::Classic);
+
+            ManiaScoreState::from(state.clone()).accuracy(classic)
+        }
+    }
+}
+
+/// The grade of a [`ScoreState`] as osu!stable or osu!lazer would award it.
+pub fn grade(
+    state: &ScoreState,
+    mode: GameMode,
+    lazer: bool,
+    mods: &GameMods,
+    slider_counts: Option<SliderCounts>,
+) -> JsGrade {
+    let acc = accuracy(state, mode, lazer, mods, slider_counts);
+
+    let grade = if lazer {
+        lazer_grade(state, mode, acc)
+    } else {
+        stable_grade(state, mode, acc)
+    };
+
+    let silver = mods.contains_intermode(GameModIntermode::Hidden)
+        || mods.contains_intermode(GameModIntermode::Flashlight)
+        || (mode == GameMode::Mania && mods.contains_intermode(GameModIntermode::FadeIn));
+
+    match grade {
+        JsGrade::SS if silver => JsGrade::SSH,
+        JsGrade::S if silver => JsGrade::SH,
+        grade => grade,
+    }
+}
+
+fn stable_grade(state: &ScoreState, mode: GameMode, acc: f64) -> JsGrade {
+    match mode {
+        GameMode::Osu | GameMode::Taiko => {
+            let total_hits = state.total_hits(mode);
+
+            if total_hits == 0 {
+                return JsGrade::D;
+            }
+
+            let ratio300 = f64::from(state.n300) / f64::from(total_hits);
+            let ratio50 = f64::from(state.n50) / f64::from(total_hits);
+            let no_miss = state.misses == 0;
+
+            if ratio300 >= 1.0 {
+                JsGrade::SS
+            } else if ratio300 > 0.9 && ratio50 <= 0.01 && no_miss {
+                JsGrade::S
+            } else if (ratio300 > 0.8 && no_miss) || ratio300 > 0.9 {
+                JsGrade::A
+            } else if (ratio300 > 0.7 && no_miss) || ratio300 > 0.8 {
+                JsGrade::B
+            } else if ratio300 > 0.6 {
+                JsGrade::C
+            } else {
+                JsGrade::D
+            }
+        }
+        GameMode::Catch => grade_from_accuracy(acc, [0.98, 0.94, 0.9, 0.85], f64::gt),
+        GameMode::Mania => grade_from_accuracy(acc, [0.95, 0.9, 0.8, 0.7], f64::gt),
+    }
+}
+
+fn lazer_grade(state: &ScoreState, mode: GameMode, acc: f64) -> JsGrade {
+    match mode {
+        GameMode::Osu | GameMode::Taiko => {
+            match grade_from_accuracy(acc, [0.95, 0.9, 0.8, 0.7], f64::ge) {
+                JsGrade::SS | JsGrade::S if state.misses > 0 => JsGrade::A,
+                grade => grade,
+            }
+        }
+        GameMode::Catch => grade_from_accuracy(acc, [0.98, 0.94, 0.9, 0.85], f64::ge),
+        GameMode::Mania => grade_from_accuracy(acc, [0.95, 0.9, 0.8, 0.7], f64::ge),
+    }
+}
+
+/// Determine the grade through accuracy thresholds for S, A, B, and C.
+fn grade_from_accuracy(
+    acc: f64,
+    [s, a, b, c]: [f64; 4],
+    reaches: fn(&f64, &f64) -> bool,
+) -> JsGrade {
+    if acc >= 1.0 {
+        JsGrade::SS
+    } else if reaches(&acc, &s) {
+        JsGrade::S
+    } else if reaches(&acc, &a) {
+        JsGrade::A
+    } else if reaches(&acc, &b) {
+        JsGrade::B
+    } else if reaches(&acc, &c) {
+        JsGrade::C
+    } else {
+        JsGrade::D
+    }
+}
+
+/// Whether slider heads are judged like regular hitcircles, i.e. whether
+/// slider accuracy is disabled.
+pub fn no_slider_head_acc(mods: &GameMods, lazer: bool) -> bool {
+    mods.iter()
+        .find_map(|gamemod| match gamemod {
+            GameMod::ClassicOsu(cl) => Some(cl.no_slider_head_accuracy.unwrap_or(true)),
+            _ => None,
+        })
+        .unwrap_or(!lazer)
+}
+
 impl JsScoreState {
     pub fn deserialize<'de, D: de::Deserializer<'de>>(d: D) -> Result<ScoreState, D::Error> {
         macro_rules! impl_deserialize {
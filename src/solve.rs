@@ -0,0 +1,263 @@
+use rosu_pp::{Performance, any::DifficultyAttributes, model::mode::GameMode};
+use wasm_bindgen::{JsCast, JsValue, prelude::wasm_bindgen};
+
+use crate::{
+    args::performance::{PerformanceArgs, SolveForPpVariable},
+    score_state::{self, SliderCounts},
+    util,
+};
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = PpSolution)]
+    pub type JsPpSolution;
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const _: &'static str = r#"/**
+* The result of `Performance.solveForPp`.
+*/
+export interface PpSolution {
+    /**
+    * Whether the target pp can be reached at all.
+    */
+    reachable: boolean;
+    /**
+    * The least demanding value that reaches the target pp, i.e. the minimum
+    * accuracy or combo, or the maximum amount of misses.
+    *
+    * Only available if the target is reachable.
+    */
+    value?: number;
+    /**
+    * The pp for `value` if the target is reachable; otherwise the highest
+    * pp that can be achieved by varying the value.
+    */
+    pp: number;
+}"#;
+
+/// Maximum amount of bisection steps for accuracy.
+const ACCURACY_ITERATIONS: usize = 50;
+
+/// Bisection stops once the accuracy interval is smaller than this.
+const ACCURACY_PRECISION: f64 = 1e-3;
+
+pub struct PpSolution {
+    value: Option<f64>,
+    pp: f64,
+}
+
+impl PpSolution {
+    const fn reachable(value: f64, pp: f64) -> Self {
+        Self {
+            value: Some(value),
+            pp,
+        }
+    }
+
+    const fn unreachable(max_pp: f64) -> Self {
+        Self {
+            value: None,
+            pp: max_pp,
+        }
+    }
+}
+
+impl From<PpSolution> for JsPpSolution {
+    fn from(solution: PpSolution) -> Self {
+        let obj = js_sys::Object::new();
+        let obj_as_ext = obj.unchecked_ref::<util::ObjectExt>();
+
+        obj_as_ext.set(
+            util::static_str_to_js("reachable"),
+            solution.value.is_some().into(),
+        );
+
+        if let Some(value) = solution.value {
+            obj_as_ext.set(util::static_str_to_js("value"), value.into());
+        }
+
+        obj_as_ext.set(util::static_str_to_js("pp"), solution.pp.into());
+
+        JsValue::from(obj).unchecked_into()
+    }
+}
+
+/// Search for the least demanding value of the variable that reaches the
+/// target pp.
+///
+/// The search bisects so it assumes that pp changes monotonically with the
+/// variable.
+pub fn solve_for_pp(
+    args: &PerformanceArgs,
+    attrs: &DifficultyAttributes,
+    target_pp: f64,
+    vary: SolveForPpVariable,
+) -> PpSolution {
+    let mut args = args.clone();
+
+    match vary {
+        SolveForPpVariable::Accuracy => {
+            // Hitresults would take precedence over accuracy
+            args.large_tick_hits = None;
+            args.small_tick_hits = None;
+            args.slider_end_hits = None;
+            args.n_geki = None;
+            args.n_katu = None;
+            args.n300 = None;
+            args.n100 = None;
+            args.n50 = None;
+
+            // Lower accuracies all result in the hitresults of the minimum
+            args.accuracy = Some(0.0);
+            let min_acc = min_accuracy(&args, attrs);
+
+            let mut pp_for = |accuracy: f64| {
+                args.accuracy = Some(accuracy);
+
+                calculate_pp(&args, attrs)
+            };
+
+            let max_pp = pp_for(100.0);
+
+            if max_pp < target_pp {
+                return PpSolution::unreachable(max_pp);
+            }
+
+            let min_pp = pp_for(0.0);
+
+            if min_pp >= target_pp {
+                return PpSolution::reachable(min_acc, min_pp);
+            }
+
+            let (mut lo, mut hi) = (min_acc, 100.0);
+            let mut hi_pp = max_pp;
+
+            for _ in 0..ACCURACY_ITERATIONS {
+                if hi - lo < ACCURACY_PRECISION {
+                    break;
+                }
+
+                let mid = (lo + hi) / 2.0;
+                let pp = pp_for(mid);
+
+                if pp >= target_pp {
+                    hi = mid;
+                    hi_pp = pp;
+                } else {
+                    lo = mid;
+                }
+            }
+
+            PpSolution::reachable(hi, hi_pp)
+        }
+        SolveForPpVariable::Misses => {
+            let mut pp_for = |misses: u32| {
+                args.misses = Some(misses);
+
+                calculate_pp(&args, attrs)
+            };
+
+            let max_pp = pp_for(0);
+
+            if max_pp < target_pp {
+                return PpSolution::unreachable(max_pp);
+            }
+
+            let max_misses = n_objects(attrs);
+            let min_pp = pp_for(max_misses);
+
+            if min_pp >= target_pp {
+                return PpSolution::reachable(f64::from(max_misses), min_pp);
+            }
+
+            // `lo` always reaches the target, `hi` never does
+            let (mut lo, mut hi) = (0, max_misses);
+            let mut lo_pp = max_pp;
+
+            while hi - lo > 1 {
+                let mid = lo + (hi - lo) / 2;
+                let pp = pp_for(mid);
+
+                if pp >= target_pp {
+                    lo = mid;
+                    lo_pp = pp;
+                } else {
+                    hi = mid;
+                }
+            }
+
+            PpSolution::reachable(f64::from(lo), lo_pp)
+        }
+        SolveForPpVariable::Combo => {
+            let mut pp_for = |combo: u32| {
+                args.combo = Some(combo);
+
+                calculate_pp(&args, attrs)
+            };
+
+            let max_combo = attrs.max_combo();
+            let max_pp = pp_for(max_combo);
+
+            if max_pp < target_pp {
+                return PpSolution::unreachable(max_pp);
+            }
+
+            let min_pp = pp_for(0);
+
+            if min_pp >= target_pp {
+                return PpSolution::reachable(0.0, min_pp);
+            }
+
+            // `hi` always reaches the target, `lo` never does
+            let (mut lo, mut hi) = (0, max_combo);
+            let mut hi_pp = max_pp;
+
+            while hi - lo > 1 {
+                let mid = lo + (hi - lo) / 2;
+                let pp = pp_for(mid);
+
+                if pp >= target_pp {
+                    hi = mid;
+                    hi_pp = pp;
+                } else {
+                    lo = mid;
+                }
+            }
+
+            PpSolution::reachable(f64::from(hi), hi_pp)
+        }
+    }
+}
+
+fn calculate_pp(args: &PerformanceArgs, attrs: &DifficultyAttributes) -> f64 {
+    args.apply(Performance::new(attrs.clone())).calculate().pp()
+}
+
+/// The accuracy between `0.0` and `100.0` of the hitresults that are
+/// generated for the arguments.
+fn min_accuracy(args: &PerformanceArgs, attrs: &DifficultyAttributes) -> f64 {
+    let state = args.apply(Performance::new(attrs.clone())).generate_state();
+
+    let mode = match attrs {
+        DifficultyAttributes::Osu(_) => GameMode::Osu,
+        DifficultyAttributes::Taiko(_) => GameMode::Taiko,
+        DifficultyAttributes::Catch(_) => GameMode::Catch,
+        DifficultyAttributes::Mania(_) => GameMode::Mania,
+    };
+
+    let lazer = args.lazer.unwrap_or(true);
+    let slider_counts = SliderCounts::from_attrs(attrs);
+
+    100.0 * score_state::accuracy(&state, mode, lazer, &args.mods, slider_counts)
+}
+
+/// The amount of objects that can be missed.
+const fn n_objects(attrs: &DifficultyAttributes) -> u32 {
+    match attrs {
+        DifficultyAttributes::Osu(attrs) => attrs.n_circles + attrs.n_sliders + attrs.n_spinners,
+        DifficultyAttributes::Taiko(attrs) => attrs.max_combo,
+        DifficultyAttributes::Catch(attrs) => attrs.n_fruits + attrs.n_droplets,
+        DifficultyAttributes::Mania(attrs) => attrs.n_objects,
+    }
+}
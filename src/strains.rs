@@ -1,21 +1,73 @@
 use rosu_pp::{
     any::Strains, catch::CatchStrains, mania::ManiaStrains, osu::OsuStrains, taiko::TaikoStrains,
 };
-use wasm_bindgen::prelude::wasm_bindgen;
+use wasm_bindgen::{JsCast, JsValue, prelude::wasm_bindgen};
 
-use crate::mode::JsGameMode;
+use crate::{
+    JsError, JsResult,
+    args::difficulty::{JsStrainsResampleArgs, StrainsResampleArgs},
+    json::{self, JsJson, JsonObject},
+    mode::JsGameMode,
+    util,
+};
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = ResampledStrains)]
+    pub type JsResampledStrains;
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const _: &'static str = r#"/**
+* The result of `Strains.resample`.
+*
+* Each array contains one value per bucket. Skill series are only available
+* for the mode they belong to.
+*/
+export interface ResampledStrains {
+    /**
+    * The strains' gamemode.
+    */
+    mode: GameMode;
+    /**
+    * Length of a bucket in ms.
+    */
+    bucketLength: number;
+    /**
+    * The center of each bucket in ms, relative to the start of the first
+    * strain section.
+    */
+    time: Float64Array;
+    /**
+    * The combined series of all skills.
+    */
+    overall: Float64Array;
+    aim?: Float64Array;
+    aimNoSliders?: Float64Array;
+    speed?: Float64Array;
+    flashlight?: Float64Array;
+    color?: Float64Array;
+    reading?: Float64Array;
+    rhythm?: Float64Array;
+    stamina?: Float64Array;
+    singleColorStamina?: Float64Array;
+    movement?: Float64Array;
+    strains?: Float64Array;
+}"#;
 
 /// The result of calculating the strains of a beatmap.
 ///
 /// Suitable to plot the difficulty over time.
-#[wasm_bindgen(js_name = Strains, getter_with_clone, inspectable)]
-#[derive(Default)]
+#[wasm_bindgen(js_name = Strains, getter_with_clone)]
+#[derive(Default, serde::Deserialize)]
+#[serde(rename = "Strains", rename_all = "camelCase")]
 pub struct JsStrains {
     /// The strains' gamemode.
     #[wasm_bindgen(readonly)]
     pub mode: JsGameMode,
     /// Time inbetween two strains in ms.
     #[wasm_bindgen(js_name = "sectionLength", readonly)]
+    #[serde(rename = "sectionLength")]
     pub section_len: f64,
     /// Strain peaks of the aim skill in osu!.
     #[wasm_bindgen(readonly)]
@@ -50,6 +102,375 @@ pub struct JsStrains {
     /// Strain peaks of the strain skill in osu!mania.
     #[wasm_bindgen(readonly)]
     pub strains: Option<Vec<f64>>,
+    /// The start time of each strain section in ms.
+    ///
+    /// Unlike `sectionLength`, the time is not adjusted by the clock rate.
+    ///
+    /// Only available if `sectionTimes` was specified for
+    /// `Difficulty.strains`.
+    #[wasm_bindgen(js_name = "sectionTimes", readonly)]
+    pub section_times: Option<Vec<f64>>,
+    /// Strains of each hitobject.
+    ///
+    /// Only available if `perObject` was specified for `Difficulty.strains`.
+    #[wasm_bindgen(readonly)]
+    pub objects: Option<JsObjectStrains>,
+}
+
+/// Strains of each hitobject alongside their start time.
+///
+/// Only the strain peaks of sections are available so the strain of a
+/// hitobject is the peak of the section that contains it.
+///
+/// For osu!taiko only circles and for osu!catch only fruits and droplets are
+/// considered hitobjects.
+#[wasm_bindgen(js_name = ObjectStrains, getter_with_clone, inspectable)]
+#[derive(Clone, Default, serde::Deserialize)]
+#[serde(rename = "ObjectStrains", rename_all = "camelCase")]
+pub struct JsObjectStrains {
+    /// The start time of each hitobject in ms.
+    ///
+    /// Unlike `Strains.sectionLength`, the time is not adjusted by the clock
+    /// rate.
+    #[wasm_bindgen(readonly)]
+    pub time: Vec<f64>,
+    /// Strains of the aim skill in osu!.
+    #[wasm_bindgen(readonly)]
+    pub aim: Option<Vec<f64>>,
+    /// Strains of the aim skill without sliders in osu!.
+    #[wasm_bindgen(js_name = "aimNoSliders", readonly)]
+    pub aim_no_sliders: Option<Vec<f64>>,
+    /// Strains of the speed skill in osu!.
+    #[wasm_bindgen(readonly)]
+    pub speed: Option<Vec<f64>>,
+    /// Strains of the flashlight skill in osu!.
+    #[wasm_bindgen(readonly)]
+    pub flashlight: Option<Vec<f64>>,
+    /// Strains of the color skill in osu!taiko.
+    #[wasm_bindgen(readonly)]
+    pub color: Option<Vec<f64>>,
+    /// Strains of the reading skill in osu!taiko.
+    #[wasm_bindgen(readonly)]
+    pub reading: Option<Vec<f64>>,
+    /// Strains of the rhythm skill in osu!taiko.
+    #[wasm_bindgen(readonly)]
+    pub rhythm: Option<Vec<f64>>,
+    /// Strains of the stamina skill in osu!taiko.
+    #[wasm_bindgen(readonly)]
+    pub stamina: Option<Vec<f64>>,
+    /// Strains of the single color stamina skill in osu!taiko.
+    #[wasm_bindgen(js_name = "singleColorStamina", readonly)]
+    pub single_color_stamina: Option<Vec<f64>>,
+    /// Strains of the movement skill in osu!catch.
+    #[wasm_bindgen(readonly)]
+    pub movement: Option<Vec<f64>>,
+    /// Strains of the strain skill in osu!mania.
+    #[wasm_bindgen(readonly)]
+    pub strains: Option<Vec<f64>>,
+}
+
+#[wasm_bindgen(js_class = Strains)]
+impl JsStrains {
+    /// Convert the strains into a plain object that includes a schema
+    /// version.
+    ///
+    /// Strain peaks of skills that are unavailable for the mode are omitted.
+    #[wasm_bindgen(js_name = toJSON)]
+    pub fn to_json(&self) -> JsJson {
+        let obj = JsonObject::versioned();
+        obj.set("mode", self.mode as u8);
+        obj.set("sectionLength", self.section_len);
+        obj.set_values("aim", self.aim.as_deref());
+        obj.set_values("aimNoSliders", self.aim_no_sliders.as_deref());
+        obj.set_values("speed", self.speed.as_deref());
+        obj.set_values("flashlight", self.flashlight.as_deref());
+        obj.set_values("color", self.color.as_deref());
+        obj.set_values("reading", self.reading.as_deref());
+        obj.set_values("rhythm", self.rhythm.as_deref());
+        obj.set_values("stamina", self.stamina.as_deref());
+        obj.set_values("singleColorStamina", self.single_color_stamina.as_deref());
+        obj.set_values("movement", self.movement.as_deref());
+        obj.set_values("strains", self.strains.as_deref());
+        obj.set_values("sectionTimes", self.section_times.as_deref());
+        obj.set_opt(
+            "objects",
+            self.objects.as_ref().map(JsObjectStrains::to_json),
+        );
+
+        obj.into()
+    }
+
+    /// Stringified version of `Strains.toJSON`.
+    #[wasm_bindgen(js_name = toString)]
+    pub fn to_json_string(&self) -> String {
+        json::stringify(&self.to_json())
+    }
+
+    /// Create strains from the result of `Strains.toJSON`.
+    /// @throws Throws an error if the JSON is invalid or of an unsupported schema version
+    #[wasm_bindgen(js_name = fromJSON)]
+    pub fn from_json(json: &JsJson) -> JsResult<JsStrains> {
+        json::check_version(json)?;
+
+        util::from_value::<JsStrains>(json)
+    }
+
+    /// Resample the strain peaks of all skills into the given amount of
+    /// buckets of equal length and add a combined `overall` series.
+    ///
+    /// Each bucket holds the highest peak of the sections it overlaps with so
+    /// that strains of different maps, modes, or mods can be charted
+    /// alongside each other.
+    ///
+    /// The `overall` series is the sum of all skills except for
+    /// `aimNoSliders` and `singleColorStamina` since those are variants of
+    /// other skills. Smoothing and normalization are applied to each skill
+    /// before they are summed up; the sum is normalized again afterwards.
+    /// @throws Throws an error if `nBuckets` is zero or the options are invalid
+    pub fn resample(
+        &self,
+        n_buckets: usize,
+        options: Option<JsStrainsResampleArgs>,
+    ) -> JsResult<JsResampledStrains> {
+        let options = options
+            .as_deref()
+            .map(util::from_value::<StrainsResampleArgs>)
+            .transpose()?
+            .unwrap_or_default();
+
+        if n_buckets == 0 {
+            return Err(JsError::new("nBuckets must be positive"));
+        }
+
+        let skills = self.skills();
+
+        let n_sections = skills
+            .iter()
+            .filter_map(|(_, peaks)| peaks.map(<[f64]>::len))
+            .max()
+            .unwrap_or(0);
+
+        let bucket_len = n_sections as f64 * self.section_len / n_buckets as f64;
+
+        let obj = js_sys::Object::new();
+        let obj_as_ext = obj.unchecked_ref::<util::ObjectExt>();
+
+        let set_values = |key: &'static str, values: &[f64]| {
+            obj_as_ext.set(
+                util::static_str_to_js(key),
+                js_sys::Float64Array::from(values).into(),
+            );
+        };
+
+        let time: Vec<_> = (0..n_buckets)
+            .map(|i| (i as f64 + 0.5) * bucket_len)
+            .collect();
+
+        obj_as_ext.set(util::static_str_to_js("mode"), (self.mode as u8).into());
+        obj_as_ext.set(util::static_str_to_js("bucketLength"), bucket_len.into());
+        set_values("time", &time);
+
+        let mut overall = vec![0.0; n_buckets];
+
+        for (key, peaks) in skills {
+            let Some(peaks) = peaks else { continue };
+
+            let mut values = smooth(&resample(peaks, n_buckets), options.smoothing as usize);
+
+            if options.normalize {
+                normalize(&mut values);
+            }
+
+            if !matches!(key, "aimNoSliders" | "singleColorStamina") {
+                overall
+                    .iter_mut()
+                    .zip(values.iter())
+                    .for_each(|(sum, value)| *sum += value);
+            }
+
+            set_values(key, &values);
+        }
+
+        if options.normalize {
+            normalize(&mut overall);
+        }
+
+        set_values("overall", &overall);
+
+        Ok(JsValue::from(obj).unchecked_into())
+    }
+}
+
+impl JsStrains {
+    /// Add the strains of each hitobject based on the hitobjects' start
+    /// times.
+    pub fn with_objects(mut self, start_times: &[f64], layout: &SectionLayout) -> Self {
+        let sections: Vec<_> = start_times
+            .iter()
+            .map(|&time| layout.section_at(time))
+            .collect();
+
+        let per_object = |peaks: &Option<Vec<f64>>| {
+            peaks.as_deref().map(|peaks| {
+                sections
+                    .iter()
+                    .map(|&i| peaks.get(i).or(peaks.last()).copied().unwrap_or(0.0))
+                    .collect()
+            })
+        };
+
+        self.objects = Some(JsObjectStrains {
+            time: start_times.to_vec(),
+            aim: per_object(&self.aim),
+            aim_no_sliders: per_object(&self.aim_no_sliders),
+            speed: per_object(&self.speed),
+            flashlight: per_object(&self.flashlight),
+            color: per_object(&self.color),
+            reading: per_object(&self.reading),
+            rhythm: per_object(&self.rhythm),
+            stamina: per_object(&self.stamina),
+            single_color_stamina: per_object(&self.single_color_stamina),
+            movement: per_object(&self.movement),
+            strains: per_object(&self.strains),
+        });
+
+        self
+    }
+
+    /// Add the start time of each section.
+    pub fn with_section_times(mut self, layout: &SectionLayout) -> Self {
+        let n_sections = self
+            .skills()
+            .iter()
+            .filter_map(|(_, peaks)| peaks.map(<[f64]>::len))
+            .max()
+            .unwrap_or(0);
+
+        let section_times = (0..n_sections)
+            .map(|section| layout.section_start(section))
+            .collect();
+
+        self.section_times = Some(section_times);
+
+        self
+    }
+
+    /// The strain peaks of each skill alongside their name in JS.
+    pub fn skills(&self) -> [(&'static str, Option<&[f64]>); 11] {
+        [
+            ("aim", self.aim.as_deref()),
+            ("aimNoSliders", self.aim_no_sliders.as_deref()),
+            ("speed", self.speed.as_deref()),
+            ("flashlight", self.flashlight.as_deref()),
+            ("color", self.color.as_deref()),
+            ("reading", self.reading.as_deref()),
+            ("rhythm", self.rhythm.as_deref()),
+            ("stamina", self.stamina.as_deref()),
+            ("singleColorStamina", self.single_color_stamina.as_deref()),
+            ("movement", self.movement.as_deref()),
+            ("strains", self.strains.as_deref()),
+        ]
+    }
+}
+
+/// The time span of strain sections in terms of the beatmap's time.
+pub struct SectionLayout {
+    first_section_end: f64,
+    section_len: f64,
+}
+
+impl SectionLayout {
+    /// Mirrors rosu-pp in that the first hitobject does not generate a strain
+    /// and that the first section ends at the next multiple of the section
+    /// length after the second hitobject.
+    pub fn new(start_times: &[f64], clock_rate: f64, section_len: f64) -> Self {
+        let first_section_end = start_times
+            .get(1)
+            .or(start_times.first())
+            .map_or(0.0, |time| {
+                (time / clock_rate / section_len).ceil() * section_len * clock_rate
+            });
+
+        Self {
+            first_section_end,
+            section_len: section_len * clock_rate,
+        }
+    }
+
+    /// The index of the section that contains the given time.
+    pub fn section_at(&self, time: f64) -> usize {
+        if time <= self.first_section_end {
+            0
+        } else {
+            ((time - self.first_section_end) / self.section_len).ceil() as usize
+        }
+    }
+
+    pub fn section_start(&self, section: usize) -> f64 {
+        self.section_end(section) - self.section_len
+    }
+
+    pub fn section_end(&self, section: usize) -> f64 {
+        self.first_section_end + section as f64 * self.section_len
+    }
+}
+
+/// The highest peak of the sections that overlap with each bucket.
+fn resample(peaks: &[f64], n_buckets: usize) -> Vec<f64> {
+    (0..n_buckets)
+        .map(|i| {
+            let start = i * peaks.len() / n_buckets;
+            let end = ((i + 1) * peaks.len()).div_ceil(n_buckets).max(start + 1);
+
+            peaks
+                .get(start..end.min(peaks.len()))
+                .map_or(0.0, |peaks| peaks.iter().copied().fold(0.0, f64::max))
+        })
+        .collect()
+}
+
+/// Centered moving average over `radius` values on each side.
+fn smooth(values: &[f64], radius: usize) -> Vec<f64> {
+    if radius == 0 {
+        return values.to_vec();
+    }
+
+    (0..values.len())
+        .map(|i| {
+            let window = &values[i.saturating_sub(radius)..(i + radius + 1).min(values.len())];
+
+            window.iter().sum::<f64>() / window.len() as f64
+        })
+        .collect()
+}
+
+/// Scale the values so that the maximum is `1`.
+fn normalize(values: &mut [f64]) {
+    let max = values.iter().copied().fold(0.0, f64::max);
+
+    if max > 0.0 {
+        values.iter_mut().for_each(|value| *value /= max);
+    }
+}
+
+impl JsObjectStrains {
+    fn to_json(&self) -> JsJson {
+        let obj = JsonObject::new();
+        obj.set_values("time", Some(&self.time));
+        obj.set_values("aim", self.aim.as_deref());
+        obj.set_values("aimNoSliders", self.aim_no_sliders.as_deref());
+        obj.set_values("speed", self.speed.as_deref());
+        obj.set_values("flashlight", self.flashlight.as_deref());
+        obj.set_values("color", self.color.as_deref());
+        obj.set_values("reading", self.reading.as_deref());
+        obj.set_values("rhythm", self.rhythm.as_deref());
+        obj.set_values("stamina", self.stamina.as_deref());
+        obj.set_values("singleColorStamina", self.single_color_stamina.as_deref());
+        obj.set_values("movement", self.movement.as_deref());
+        obj.set_values("strains", self.strains.as_deref());
+
+        obj.into()
+    }
 }
 
 impl From<Strains> for JsStrains {
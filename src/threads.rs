@@ -0,0 +1,88 @@
+use std::{io, sync::OnceLock};
+
+use crossbeam_channel::Receiver;
+use js_sys::{JsString, Promise};
+use rayon::{ThreadBuilder, ThreadPoolBuilder};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+
+use crate::{JsError, JsResult};
+
+#[wasm_bindgen(module = "/js/threads.js")]
+extern "C" {
+    #[wasm_bindgen(js_name = startWorkers)]
+    fn start_workers(
+        module: JsValue,
+        memory: JsValue,
+        main_js: JsString,
+        num_threads: usize,
+    ) -> Promise;
+}
+
+#[wasm_bindgen]
+extern "C" {
+    /// URL of the generated main module which the workers have to import.
+    #[wasm_bindgen(thread_local_v2, js_namespace = ["import", "meta"], js_name = url)]
+    static MAIN_JS: JsString;
+}
+
+/// Rayon's threads which are sent to the workers once the pool is built.
+///
+/// Uses crossbeam's channel because blocked receivers of std's channel are not
+/// woken up on workers that were spawned through JS.
+static THREADS: OnceLock<Receiver<ThreadBuilder>> = OnceLock::new();
+
+/// Spawn `numThreads` workers and use them as thread pool for batch
+/// calculations.
+///
+/// Workers are spawned through `node:worker_threads` in Node.js and as Web
+/// Workers otherwise.
+/// @throws Throws an error if `numThreads` is zero or if the pool was already initialized
+#[wasm_bindgen(js_name = initThreadPool)]
+pub async fn init_thread_pool(num_threads: usize) -> JsResult<()> {
+    if num_threads == 0 {
+        return Err(JsError::new("numThreads must be positive"));
+    }
+
+    let (sender, receiver) = crossbeam_channel::bounded(num_threads);
+
+    if THREADS.set(receiver).is_err() {
+        return Err(JsError::new("The thread pool was already initialized"));
+    }
+
+    let main_js = MAIN_JS.with(Clone::clone);
+    let workers = start_workers(
+        wasm_bindgen::module(),
+        wasm_bindgen::memory(),
+        main_js,
+        num_threads,
+    );
+
+    JsFuture::from(workers).await?;
+
+    // All workers wait for a thread at this point so sending does not block
+    ThreadPoolBuilder::new()
+        .num_threads(num_threads)
+        .spawn_handler(|thread| {
+            sender
+                .send(thread)
+                .map_err(|_| io::Error::other("thread pool was dropped"))
+        })
+        .build_global()
+        .map_err(|err| JsError::new(&err.to_string()))
+}
+
+/// Run one of rayon's threads on the calling worker.
+///
+/// Only called by the workers that were spawned in `initThreadPool`.
+#[doc(hidden)]
+#[wasm_bindgen(js_name = startPoolWorker, skip_typescript)]
+pub fn start_pool_worker() {
+    let thread = THREADS
+        .get()
+        .expect_throw("thread pool is not initialized")
+        .recv()
+        .expect_throw("thread pool was dropped");
+
+    thread.run();
+}
@@ -0,0 +1,420 @@
+use rosu_mods::{GameModIntermode, GameMods};
+use rosu_pp::any::{DifficultyAttributes, ScoreState};
+use wasm_bindgen::{JsCast, JsValue, prelude::wasm_bindgen};
+
+use crate::{args::performance::PerformanceArgs, score_state, util};
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = ScoreValidation)]
+    pub type JsScoreValidation;
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const _: &'static str = r#"/**
+* The result of validating hitresults and combo against a beatmap.
+*/
+export interface ScoreValidation {
+    /**
+    * Whether there are no errors. Warnings do not invalidate a score.
+    */
+    valid: boolean;
+    /**
+    * Values that are impossible for the beatmap.
+    */
+    errors: ValidationIssue[];
+    /**
+    * Values that are possible but suspicious or ignored.
+    */
+    warnings: ValidationIssue[];
+}
+
+/**
+* A single problem found during validation.
+*/
+export interface ValidationIssue {
+    /**
+    * The name of the field that the issue refers to.
+    *
+    * Issues about the sum of multiple hitresults use `totalHits` or, for
+    * tiny droplets in osu!catch, `totalTinyDroplets`.
+    */
+    field: string;
+    /**
+    * The value of the field.
+    */
+    value: number;
+    /**
+    * The maximum possible value for the field, if there is one.
+    */
+    max?: number;
+    /**
+    * Human-readable description of the issue.
+    */
+    message: string;
+}"#;
+
+/// A named hitresult or combo value that is to be validated.
+///
+/// Values that were not specified are `None` and only considered as `0` when
+/// summing up hitresults.
+#[derive(Copy, Clone)]
+pub struct Field {
+    name: &'static str,
+    value: Option<u32>,
+}
+
+impl Field {
+    const fn new(name: &'static str, value: Option<u32>) -> Self {
+        Self { name, value }
+    }
+}
+
+/// The values of a score that can be validated.
+pub struct ScoreFields {
+    combo: Field,
+    large_tick_hits: Field,
+    small_tick_hits: Field,
+    slider_end_hits: Field,
+    n_geki: Field,
+    n_katu: Field,
+    n300: Field,
+    n100: Field,
+    n50: Field,
+    misses: Field,
+}
+
+impl ScoreFields {
+    pub const fn from_state(state: &ScoreState) -> Self {
+        Self {
+            combo: Field::new("maxCombo", Some(state.max_combo)),
+            large_tick_hits: Field::new("osuLargeTickHits", Some(state.osu_large_tick_hits)),
+            small_tick_hits: Field::new("osuSmallTickHits", Some(state.osu_small_tick_hits)),
+            slider_end_hits: Field::new("sliderEndHits", Some(state.slider_end_hits)),
+            n_geki: Field::new("nGeki", Some(state.n_geki)),
+            n_katu: Field::new("nKatu", Some(state.n_katu)),
+            n300: Field::new("n300", Some(state.n300)),
+            n100: Field::new("n100", Some(state.n100)),
+            n50: Field::new("n50", Some(state.n50)),
+            misses: Field::new("misses", Some(state.misses)),
+        }
+    }
+
+    pub const fn from_args(args: &PerformanceArgs) -> Self {
+        Self {
+            combo: Field::new("combo", args.combo),
+            large_tick_hits: Field::new("largeTickHits", args.large_tick_hits),
+            small_tick_hits: Field::new("smallTickHits", args.small_tick_hits),
+            slider_end_hits: Field::new("sliderEndHits", args.slider_end_hits),
+            n_geki: Field::new("nGeki", args.n_geki),
+            n_katu: Field::new("nKatu", args.n_katu),
+            n300: Field::new("n300", args.n300),
+            n100: Field::new("n100", args.n100),
+            n50: Field::new("n50", args.n50),
+            misses: Field::new("misses", args.misses),
+        }
+    }
+}
+
+struct Issue {
+    field: &'static str,
+    value: u32,
+    max: Option<u32>,
+    message: String,
+}
+
+impl Issue {
+    fn into_js(self) -> JsValue {
+        let obj = js_sys::Object::new();
+        let obj_as_ext = obj.unchecked_ref::<util::ObjectExt>();
+
+        let set = |key, value| obj_as_ext.set(util::static_str_to_js(key), value);
+
+        set("field", util::static_str_to_js(self.field).into());
+        set("value", self.value.into());
+
+        if let Some(max) = self.max {
+            set("max", max.into());
+        }
+
+        set("message", self.message.into());
+
+        obj.into()
+    }
+}
+
+/// Collects errors and warnings while validating.
+#[derive(Default)]
+pub struct Validation {
+    errors: Vec<Issue>,
+    warnings: Vec<Issue>,
+}
+
+impl Validation {
+    /// Validate the fields of a score against the object counts of the
+    /// difficulty attributes.
+    pub fn new(
+        fields: &ScoreFields,
+        attrs: &DifficultyAttributes,
+        lazer: bool,
+        mods: &GameMods,
+    ) -> Self {
+        let mut this = Self::default();
+
+        match attrs {
+            DifficultyAttributes::Osu(attrs) => {
+                let n_objects = attrs.n_circles + attrs.n_sliders + attrs.n_spinners;
+                let hits = [fields.n300, fields.n100, fields.n50, fields.misses];
+
+                if this.all_at_most(&hits, n_objects, "objects") {
+                    this.total_at_most("totalHits", &hits, n_objects, "objects");
+                }
+
+                let no_slider_head_acc = score_state::no_slider_head_acc(mods, lazer);
+
+                let max_large_ticks = if no_slider_head_acc {
+                    attrs.n_sliders + attrs.n_large_ticks
+                } else {
+                    attrs.n_large_ticks
+                };
+
+                let large_ticks = if no_slider_head_acc {
+                    "slider heads and large ticks"
+                } else {
+                    "large ticks"
+                };
+
+                this.at_most(fields.large_tick_hits, max_large_ticks, large_ticks);
+                this.at_most(fields.small_tick_hits, attrs.n_sliders, "sliders");
+                this.at_most(fields.slider_end_hits, attrs.n_sliders, "sliders");
+
+                // Combo includes slider ticks so it is not bounded by hits
+                this.with_combo(fields, None, attrs.max_combo)
+            }
+            DifficultyAttributes::Taiko(attrs) => {
+                let hits = [fields.n300, fields.n100, fields.misses];
+
+                if this.all_at_most(&hits, attrs.max_combo, "hit circles") {
+                    this.total_at_most("totalHits", &hits, attrs.max_combo, "hit circles");
+                }
+
+                this.unused(fields.n50, "osu!taiko");
+                this.unused_slider_fields(fields, "osu!taiko");
+
+                this.with_combo(fields, Some(&[fields.n300, fields.n100]), attrs.max_combo)
+            }
+            DifficultyAttributes::Catch(attrs) => {
+                let combo_objects = attrs.n_fruits + attrs.n_droplets;
+                let hits = [fields.n300, fields.n100, fields.misses];
+
+                let valid = [
+                    this.at_most(fields.n300, attrs.n_fruits, "fruits"),
+                    this.at_most(fields.n100, attrs.n_droplets, "droplets"),
+                    this.at_most(fields.misses, combo_objects, "fruits and droplets"),
+                ];
+
+                if valid.iter().all(|valid| *valid) {
+                    this.total_at_most("totalHits", &hits, combo_objects, "fruits and droplets");
+                }
+
+                let tiny_droplets = [fields.n50, fields.n_katu];
+
+                if this.all_at_most(&tiny_droplets, attrs.n_tiny_droplets, "tiny droplets") {
+                    this.total_at_most(
+                        "totalTinyDroplets",
+                        &tiny_droplets,
+                        attrs.n_tiny_droplets,
+                        "tiny droplets",
+                    );
+                }
+
+                this.unused_slider_fields(fields, "osu!catch");
+
+                this.with_combo(fields, Some(&[fields.n300, fields.n100]), combo_objects)
+            }
+            DifficultyAttributes::Mania(attrs) => {
+                let classic = !lazer || mods.contains_intermode(GameModIntermode::Classic);
+
+                let (n_objects, objects) = if classic {
+                    (attrs.n_objects, "notes")
+                } else {
+                    (
+                        attrs.n_objects + attrs.n_hold_notes,
+                        "notes and hold note tails",
+                    )
+                };
+
+                let hits = [
+                    fields.n_geki,
+                    fields.n300,
+                    fields.n_katu,
+                    fields.n100,
+                    fields.n50,
+                    fields.misses,
+                ];
+
+                if this.all_at_most(&hits, n_objects, objects) {
+                    this.total_at_most("totalHits", &hits, n_objects, objects);
+                }
+
+                this.unused_slider_fields(fields, "osu!mania");
+
+                // Combo is irrelevant for osu!mania
+                this
+            }
+        }
+    }
+
+    /// Validate the combo against the maximum combo and, if given, the
+    /// amount of hit objects that contribute to combo.
+    fn with_combo(
+        mut self,
+        fields: &ScoreFields,
+        combo_hits: Option<&[Field]>,
+        max_combo: u32,
+    ) -> Self {
+        let Some(combo) = fields.combo.value else {
+            return self;
+        };
+
+        if combo > max_combo {
+            self.errors.push(Issue {
+                field: fields.combo.name,
+                value: combo,
+                max: Some(max_combo),
+                message: format!("Combo of {combo} exceeds the maximum combo of {max_combo}"),
+            });
+        } else if combo == max_combo && fields.misses.value.is_some_and(|misses| misses > 0) {
+            self.warnings.push(Issue {
+                field: fields.combo.name,
+                value: combo,
+                max: Some(max_combo),
+                message: "Full combo despite misses".to_owned(),
+            });
+        }
+
+        let Some(combo_hits) = combo_hits else {
+            return self;
+        };
+
+        let hit_objects = sum(combo_hits);
+        let hits_specified = combo_hits.iter().all(|field| field.value.is_some());
+
+        if hits_specified && combo > hit_objects && combo <= max_combo {
+            self.errors.push(Issue {
+                field: fields.combo.name,
+                value: combo,
+                max: Some(hit_objects),
+                message: format!("Combo of {combo} exceeds the {hit_objects} hit objects"),
+            });
+        }
+
+        self
+    }
+
+    /// Returns `false` if the field's value exceeds `max`.
+    fn at_most(&mut self, field: Field, max: u32, objects: &str) -> bool {
+        match field.value {
+            Some(value) if value > max => {
+                self.errors.push(Issue {
+                    field: field.name,
+                    value,
+                    max: Some(max),
+                    message: format!(
+                        "`{}` is {value} but the beatmap only has {max} {objects}",
+                        field.name
+                    ),
+                });
+
+                false
+            }
+            _ => true,
+        }
+    }
+
+    /// Returns `false` if any field's value exceeds `max`.
+    fn all_at_most(&mut self, fields: &[Field], max: u32, objects: &str) -> bool {
+        // Intentionally not short-circuiting so every field is checked
+        let invalid = fields
+            .iter()
+            .filter(|field| !self.at_most(**field, max, objects))
+            .count();
+
+        invalid == 0
+    }
+
+    /// Adds an error if the sum of the fields exceeds `max` and a warning if
+    /// all fields are specified but their sum is less than `max`.
+    fn total_at_most(&mut self, name: &'static str, fields: &[Field], max: u32, objects: &str) {
+        let total = sum(fields);
+
+        if total > max {
+            self.errors.push(Issue {
+                field: name,
+                value: total,
+                max: Some(max),
+                message: format!("{total} hitresults but the beatmap only has {max} {objects}"),
+            });
+        } else if total < max && fields.iter().all(|field| field.value.is_some()) {
+            self.warnings.push(Issue {
+                field: name,
+                value: total,
+                max: Some(max),
+                message: format!(
+                    "Only {total} hitresults for {max} {objects}; the score is considered \
+                    to be incomplete"
+                ),
+            });
+        }
+    }
+
+    fn unused(&mut self, field: Field, mode: &str) {
+        if let Some(value) = field.value.filter(|value| *value > 0) {
+            self.warnings.push(Issue {
+                field: field.name,
+                value,
+                max: None,
+                message: format!("`{}` is ignored in {mode}", field.name),
+            });
+        }
+    }
+
+    fn unused_slider_fields(&mut self, fields: &ScoreFields, mode: &str) {
+        self.unused(fields.large_tick_hits, mode);
+        self.unused(fields.small_tick_hits, mode);
+        self.unused(fields.slider_end_hits, mode);
+    }
+}
+
+impl From<Validation> for JsScoreValidation {
+    fn from(validation: Validation) -> Self {
+        let obj = js_sys::Object::new();
+        let obj_as_ext = obj.unchecked_ref::<util::ObjectExt>();
+
+        let issues = |issues: Vec<Issue>| -> js_sys::Array {
+            issues.into_iter().map(Issue::into_js).collect()
+        };
+
+        obj_as_ext.set(
+            util::static_str_to_js("valid"),
+            validation.errors.is_empty().into(),
+        );
+        obj_as_ext.set(
+            util::static_str_to_js("errors"),
+            issues(validation.errors).into(),
+        );
+        obj_as_ext.set(
+            util::static_str_to_js("warnings"),
+            issues(validation.warnings).into(),
+        );
+
+        JsValue::from(obj).unchecked_into()
+    }
+}
+
+fn sum(fields: &[Field]) -> u32 {
+    fields
+        .iter()
+        .filter_map(|field| field.value)
+        .fold(0, u32::saturating_add)
+}